@@ -0,0 +1,552 @@
+//! # crates-llms-txt
+//!
+//! Command-line interface for the `crates_llms_txt` library. It fetches or
+//! generates rustdoc JSON for a crate and writes the resulting `llms.txt` and
+//! `llms-full.txt` files (or the raw `CrateDocs` as JSON) to a directory.
+//!
+//! ## Examples
+//!
+//! ```text
+//! crates-llms-txt online clap --version 4.5.39 -o ./docs
+//! crates-llms-txt online clap --cache-dir ~/.cache/crates-llms-txt
+//! crates-llms-txt url https://docs.rs/crate/serde/latest/json
+//! crates-llms-txt url https://docs.example.com/crate/demo/1.0.0/json \
+//!   --auth-header 'docs.example.com=Authorization: Bearer TOKEN'
+//! crates-llms-txt local --manifest-path ./Cargo.toml --toolchain stable
+//! crates-llms-txt local-features --features async,json --no-default-features
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use crates_llms_txt::cache::DocsCache;
+use crates_llms_txt::error::Result;
+use crates_llms_txt::fetch_docs::OnlineDocs;
+use crates_llms_txt::options::{
+  parse_item_kind, DeprecatedItems, ItemKind, ProcessOptions,
+};
+use crates_llms_txt::CrateDocs;
+
+/// File name used when writing `CrateDocs` as JSON
+const JSON_FILE_NAME: &str = "llms.json";
+
+/// Generate llms.txt and llms-full.txt files for Rust crates.
+#[derive(Debug, Parser)]
+#[command(name = "crates-llms-txt", version, about)]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+
+  /// Directory the generated files are written to
+  #[arg(short, long, global = true, default_value = ".")]
+  output: PathBuf,
+
+  /// Output format of the generated files
+  #[arg(short, long, global = true, value_enum, default_value_t = Format::Txt)]
+  format: Format,
+
+  #[command(flatten)]
+  process: ProcessArgs,
+}
+
+/// Flags controlling which items are included, mirroring `ProcessOptions`.
+#[derive(Debug, Default, clap::Args)]
+struct ProcessArgs {
+  /// Include public items without doc comments, using their signature
+  #[arg(long, global = true)]
+  include_undocumented: bool,
+
+  /// Include crate-visible items and items of private modules
+  #[arg(long, global = true)]
+  include_private: bool,
+
+  /// Leave out items marked #[deprecated]
+  #[arg(long, global = true, conflicts_with = "deprecated_items")]
+  exclude_deprecated: bool,
+
+  /// How to list items marked #[deprecated]: "include", "optional" (in the
+  /// Optional section) or "exclude"
+  #[arg(long, global = true, value_parser = parse_deprecated_items)]
+  deprecated_items: Option<DeprecatedItems>,
+
+  /// Include items marked #[doc(hidden)]
+  #[arg(long, global = true)]
+  include_hidden: bool,
+
+  /// Leave auto trait (Send, Sync, ...) and blanket impls out of the trait
+  /// implementations listed under a type
+  #[arg(long, global = true)]
+  exclude_blanket_impls: bool,
+
+  /// Comma-separated item kinds to include (e.g. "struct,trait,function")
+  #[arg(long, global = true, value_delimiter = ',', value_parser = parse_kind)]
+  include_kinds: Vec<ItemKind>,
+
+  /// Comma-separated item kinds to leave out
+  #[arg(long, global = true, value_delimiter = ',', value_parser = parse_kind)]
+  exclude_kinds: Vec<ItemKind>,
+
+  /// Glob pattern of item paths to include (e.g. "clap::builder::**")
+  #[arg(long = "include-path", global = true)]
+  include_paths: Vec<String>,
+
+  /// Glob pattern of item paths to leave out
+  #[arg(long = "exclude-path", global = true)]
+  exclude_paths: Vec<String>,
+
+  /// Maximum number of items listed after the crate root
+  #[arg(long, global = true)]
+  max_items: Option<usize>,
+
+  /// Comma-separated cargo features; leave out items that require others
+  #[arg(long, global = true, value_delimiter = ',')]
+  enabled_features: Option<Vec<String>>,
+}
+
+impl ProcessArgs {
+  /// Build the library options from the command-line flags.
+  fn to_options(&self) -> ProcessOptions {
+    let mut builder = ProcessOptions::builder()
+      .include_undocumented(self.include_undocumented)
+      .includes_private(self.include_private)
+      .include_deprecated(!self.exclude_deprecated)
+      .include_hidden(self.include_hidden)
+      .include_blanket_impls(!self.exclude_blanket_impls);
+    if let Some(mode) = self.deprecated_items {
+      builder = builder.deprecated_items(mode);
+    }
+    for kind in &self.include_kinds {
+      builder = builder.include_kind(*kind);
+    }
+    for kind in &self.exclude_kinds {
+      builder = builder.exclude_kind(*kind);
+    }
+    for pattern in &self.include_paths {
+      builder = builder.include_path(pattern);
+    }
+    for pattern in &self.exclude_paths {
+      builder = builder.exclude_path(pattern);
+    }
+    if let Some(max_items) = self.max_items {
+      builder = builder.max_items(max_items);
+    }
+    if let Some(features) = &self.enabled_features {
+      builder = builder.enabled_features(features);
+    }
+    builder.build()
+  }
+}
+
+/// Parse an item kind given on the command line.
+fn parse_kind(name: &str) -> std::result::Result<ItemKind, String> {
+  parse_item_kind(name).ok_or_else(|| format!("unknown item kind `{name}`"))
+}
+
+/// Parse a deprecated items mode given on the command line.
+fn parse_deprecated_items(
+  name: &str,
+) -> std::result::Result<DeprecatedItems, String> {
+  DeprecatedItems::parse(name)
+    .ok_or_else(|| format!("unknown deprecated items mode `{name}`"))
+}
+
+/// Flags configuring the HTTP client of the online subcommands, mirroring
+/// `OnlineDocsBuilder`.
+#[derive(Debug, Default, clap::Args)]
+struct NetworkArgs {
+  /// Maximum time in seconds a request may take, including the download
+  #[arg(long, value_name = "SECS")]
+  timeout: Option<u64>,
+
+  /// Maximum time in seconds to connect to a server
+  #[arg(long, value_name = "SECS")]
+  connect_timeout: Option<u64>,
+
+  /// User-Agent sent with every request
+  #[arg(long)]
+  user_agent: Option<String>,
+
+  /// Proxy every request goes through (e.g. "http://proxy:8080")
+  #[arg(long)]
+  proxy: Option<String>,
+
+  /// PEM file of a root certificate to trust in addition to the system ones
+  #[arg(long = "root-certificate", value_name = "PATH")]
+  root_certificates: Vec<PathBuf>,
+
+  /// Header sent to one host only, as "HOST=NAME: VALUE" (e.g.
+  /// "docs.example.com=Authorization: Bearer TOKEN")
+  #[arg(long = "auth-header", value_parser = parse_auth_header)]
+  auth_headers: Vec<(String, String, String)>,
+}
+
+impl NetworkArgs {
+  /// Build the HTTP client from the command-line flags.
+  fn to_online(&self) -> Result<OnlineDocs> {
+    let mut builder = OnlineDocs::builder();
+    if let Some(secs) = self.timeout {
+      builder = builder.timeout(Duration::from_secs(secs));
+    }
+    if let Some(secs) = self.connect_timeout {
+      builder = builder.connect_timeout(Duration::from_secs(secs));
+    }
+    if let Some(user_agent) = &self.user_agent {
+      builder = builder.user_agent(user_agent);
+    }
+    if let Some(proxy) = &self.proxy {
+      builder = builder.proxy(proxy);
+    }
+    for path in &self.root_certificates {
+      builder = builder.root_certificate(fs::read(path)?);
+    }
+    for (host, name, value) in &self.auth_headers {
+      builder = builder.auth_header(host, name, value);
+    }
+    builder.build()
+  }
+}
+
+/// Parse a "HOST=NAME: VALUE" auth header given on the command line.
+fn parse_auth_header(
+  header: &str,
+) -> std::result::Result<(String, String, String), String> {
+  let parsed = header.split_once('=').and_then(|(host, header)| {
+    let (name, value) = header.split_once(':')?;
+    Some((host.trim(), name.trim(), value.trim()))
+  });
+  match parsed {
+    Some((host, name, value)) if !host.is_empty() && !name.is_empty() => {
+      Ok((host.to_string(), name.to_string(), value.to_string()))
+    }
+    _ => Err(format!("expected HOST=NAME: VALUE, got `{header}`")),
+  }
+}
+
+/// Documentation sources, mirroring the `CrateDocs::from_*` constructors.
+#[derive(Debug, Subcommand)]
+enum Command {
+  /// Fetch documentation for a published crate from docs.rs
+  Online {
+    /// Name of the crate (e.g. "serde", "clap")
+    lib_name: String,
+
+    /// Version of the crate or a semver requirement (e.g. "~4.5"); defaults
+    /// to the latest release
+    #[arg(long)]
+    version: Option<String>,
+
+    /// Directory keeping downloaded rustdoc JSON between runs
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Only read documentation from the cache, never from docs.rs
+    #[arg(long, requires = "cache_dir")]
+    offline: bool,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+  },
+
+  /// Fetch documentation from a rustdoc JSON endpoint
+  Url {
+    /// Complete URL to the rustdoc JSON (e.g. "https://docs.rs/crate/clap/latest/json")
+    url: String,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+  },
+
+  /// Generate documentation for a local crate with all features enabled
+  Local {
+    /// Path to the Cargo.toml of the crate
+    #[arg(long, default_value = "Cargo.toml")]
+    manifest_path: PathBuf,
+
+    /// Rust toolchain to use ("stable", "nightly"); auto-detected if omitted
+    #[arg(long)]
+    toolchain: Option<String>,
+  },
+
+  /// Generate documentation for a local crate with a custom feature set
+  LocalFeatures {
+    /// Path to the Cargo.toml of the crate
+    #[arg(long, default_value = "Cargo.toml")]
+    manifest_path: PathBuf,
+
+    /// Comma-separated list of features to enable
+    #[arg(long, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Disable the default features of the crate
+    #[arg(long)]
+    no_default_features: bool,
+
+    /// Rust toolchain to use ("stable", "nightly"); auto-detected if omitted
+    #[arg(long)]
+    toolchain: Option<String>,
+  },
+}
+
+/// Output formats supported by the command-line interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+  /// `llms.txt` and `llms-full.txt`
+  Txt,
+  /// `CrateDocs` serialized as pretty-printed JSON
+  Json,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  let cli = Cli::parse();
+
+  match run(cli).await {
+    Ok(paths) => {
+      for path in paths {
+        println!("{}", path.display());
+      }
+      ExitCode::SUCCESS
+    }
+    Err(err) => {
+      eprintln!("error: {err}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// Load the documentation selected by the subcommand and write it to disk.
+async fn run(cli: Cli) -> Result<Vec<PathBuf>> {
+  let options = cli.process.to_options();
+  let docs = match cli.command {
+    Command::Online {
+      lib_name,
+      version,
+      cache_dir: Some(cache_dir),
+      offline,
+      network,
+    } => {
+      let online = network.to_online()?;
+      let cache = DocsCache::new(cache_dir).offline(offline);
+      CrateDocs::from_online_cached(
+        &lib_name, version, &online, &cache, &options,
+      )
+      .await?
+    }
+    Command::Online {
+      lib_name,
+      version,
+      network,
+      ..
+    } => {
+      let online = network.to_online()?;
+      CrateDocs::from_online(&lib_name, version, &online, &options).await?
+    }
+    Command::Url { url, network } => {
+      CrateDocs::from_url(&url, &network.to_online()?, &options).await?
+    }
+    Command::Local {
+      manifest_path,
+      toolchain,
+    } => CrateDocs::from_local(manifest_path, toolchain, &options)?,
+    Command::LocalFeatures {
+      manifest_path,
+      features,
+      no_default_features,
+      toolchain,
+    } => CrateDocs::from_local_with_features(
+      manifest_path,
+      no_default_features,
+      (!features.is_empty()).then_some(features),
+      toolchain,
+      &options,
+    )?,
+  };
+
+  for warning in &docs.warnings {
+    eprintln!("warning: {warning}");
+  }
+
+  write_output(&docs, &cli.output, cli.format)
+}
+
+/// Write the documentation in the requested format into `dir`.
+fn write_output(
+  docs: &CrateDocs,
+  dir: &Path,
+  format: Format,
+) -> Result<Vec<PathBuf>> {
+  match format {
+    Format::Txt => docs.write_to_dir(dir),
+    Format::Json => {
+      fs::create_dir_all(dir)?;
+      let path = dir.join(JSON_FILE_NAME);
+      fs::write(&path, serde_json::to_string_pretty(docs)?)?;
+      Ok(vec![path])
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use clap::CommandFactory;
+
+  #[test]
+  fn test_cli_definition() {
+    Cli::command().debug_assert();
+  }
+
+  #[test]
+  fn test_parse_local_features() {
+    let cli = Cli::try_parse_from([
+      "crates-llms-txt",
+      "local-features",
+      "--features",
+      "async,json",
+      "--no-default-features",
+      "-o",
+      "out",
+      "--format",
+      "json",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.output, PathBuf::from("out"));
+    assert_eq!(cli.format, Format::Json);
+    match cli.command {
+      Command::LocalFeatures {
+        features,
+        no_default_features,
+        ..
+      } => {
+        assert_eq!(features, vec!["async", "json"]);
+        assert!(no_default_features);
+      }
+      command => panic!("unexpected command: {command:?}"),
+    }
+  }
+
+  #[test]
+  fn test_parse_process_options() {
+    let cli = Cli::try_parse_from([
+      "crates-llms-txt",
+      "online",
+      "demo",
+      "--include-undocumented",
+    ])
+    .unwrap();
+    assert!(cli.process.to_options().include_undocumented);
+
+    let cli = Cli::try_parse_from([
+      "crates-llms-txt",
+      "url",
+      "https://docs.rs/crate/demo/latest/json",
+      "--exclude-kinds",
+      "macro,type_alias",
+      "--include-path",
+      "demo::**",
+      "--exclude-deprecated",
+      "--max-items",
+      "10",
+      "--enabled-features",
+      "json,tls",
+      "--exclude-blanket-impls",
+    ])
+    .unwrap();
+    let options = cli.process.to_options();
+    assert_eq!(
+      options.exclude_kinds,
+      vec![ItemKind::Macro, ItemKind::TypeAlias]
+    );
+    assert_eq!(options.include_paths, vec!["demo::**"]);
+    assert_eq!(options.deprecated_items, DeprecatedItems::Exclude);
+    assert_eq!(options.max_items, Some(10));
+    assert!(!options.include_blanket_impls);
+    assert_eq!(
+      options.enabled_features,
+      Some(vec!["json".to_string(), "tls".to_string()])
+    );
+
+    assert!(Cli::try_parse_from([
+      "crates-llms-txt",
+      "online",
+      "demo",
+      "--include-kinds",
+      "class",
+    ])
+    .is_err());
+
+    let cli = Cli::try_parse_from([
+      "crates-llms-txt",
+      "online",
+      "demo",
+      "--deprecated-items",
+      "optional",
+    ])
+    .unwrap();
+    assert_eq!(
+      cli.process.to_options().deprecated_items,
+      DeprecatedItems::Optional
+    );
+
+    let cli =
+      Cli::try_parse_from(["crates-llms-txt", "online", "demo"]).unwrap();
+    assert_eq!(cli.process.to_options(), ProcessOptions::default());
+  }
+
+  #[test]
+  fn test_parse_network_args() {
+    let cli = Cli::try_parse_from([
+      "crates-llms-txt",
+      "url",
+      "https://docs.example.com/crate/demo/1.0.0/json",
+      "--timeout",
+      "60",
+      "--proxy",
+      "http://proxy:8080",
+      "--auth-header",
+      "docs.example.com=Authorization: Bearer a:b",
+    ])
+    .unwrap();
+    let Command::Url { network, .. } = cli.command else {
+      panic!("unexpected command: {:?}", cli.command);
+    };
+    assert_eq!(network.timeout, Some(60));
+    assert_eq!(network.proxy.as_deref(), Some("http://proxy:8080"));
+    assert_eq!(
+      network.auth_headers,
+      vec![(
+        "docs.example.com".to_string(),
+        "Authorization".to_string(),
+        "Bearer a:b".to_string()
+      )]
+    );
+    assert!(network.to_online().is_ok());
+
+    assert!(Cli::try_parse_from([
+      "crates-llms-txt",
+      "online",
+      "demo",
+      "--auth-header",
+      "Authorization: Bearer TOKEN",
+    ])
+    .is_err());
+  }
+
+  #[test]
+  fn test_write_output_json() {
+    let dir = std::env::temp_dir()
+      .join(format!("crates_llms_txt_cli_{}", std::process::id()));
+    let docs = CrateDocs::new("demo", "0.1.0");
+
+    let paths = write_output(&docs, &dir, Format::Json).unwrap();
+    let written: CrateDocs =
+      serde_json::from_str(&fs::read_to_string(&paths[0]).unwrap()).unwrap();
+    assert_eq!(written.lib_name, "demo");
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -7,6 +7,7 @@
  *
  * @param libName - The name of the crate as it appears on crates.io (e.g., "clap", "serde", "tokio")
  * @param version - Optional version string. If not provided or null, the latest version will be fetched
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if the crate is not found or an error occurs
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -25,7 +26,7 @@
  * }
  * ```
  */
-export declare function fromCrateName(libName: string, version?: string | undefined | null): Promise<LlMsConfig | null>
+export declare function fromCrateName(libName: string, version?: string | undefined | null, options?: ProcessOptions | undefined | null): Promise<LlMsConfig | null>
 
 /**
  * Generates documentation for a local Rust crate by running `cargo doc --all-features`
@@ -34,6 +35,7 @@ export declare function fromCrateName(libName: string, version?: string | undefi
  *
  * @param manifestPath - Absolute or relative path to the Cargo.toml file of the target crate
  * @param toolchain - Optional Rust toolchain to use (e.g., "stable", "nightly", "1.70.0"). If not provided, uses system default
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails or toolchain is unavailable
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -53,7 +55,7 @@ export declare function fromCrateName(libName: string, version?: string | undefi
  * }
  * ```
  */
-export declare function fromLocal(manifestPath: string, toolchain?: string | undefined | null): LlMsConfig | null
+export declare function fromLocal(manifestPath: string, toolchain?: string | undefined | null, options?: ProcessOptions | undefined | null): LlMsConfig | null
 
 /**
  * Unified function to generate local crate documentation using rustdoc with flexible feature configuration
@@ -63,6 +65,7 @@ export declare function fromLocal(manifestPath: string, toolchain?: string | und
  * @param params - A union type that accepts either:
  *   - LLMsConfigRustdocByAllFeatures: { toolchain?: string, manifestPath: string }
  *   - LLMsConfigRustdocByFeatures: { toolchain?: string, manifestPath: string, noDefaultFeatures: boolean, features?: string[] }
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -92,7 +95,7 @@ export declare function fromLocal(manifestPath: string, toolchain?: string | und
  * }
  * ```
  */
-export declare function fromLocalByRustdoc(params: LlMsConfigRustdocByAllFeatures | LlMsConfigRustdocByFeatures): LlMsConfig | null
+export declare function fromLocalByRustdoc(params: LlMsConfigRustdocByAllFeatures | LlMsConfigRustdocByFeatures, options?: ProcessOptions | undefined | null): LlMsConfig | null
 
 /**
  * Generates documentation for a local Rust crate with fine-grained feature control
@@ -103,6 +106,7 @@ export declare function fromLocalByRustdoc(params: LlMsConfigRustdocByAllFeature
  * @param noDefaultFeatures - If true, disables all default features of the crate (equivalent to --no-default-features)
  * @param features - Optional array of specific feature names to enable (e.g., ["async", "serde", "tokio"])
  * @param toolchain - Optional Rust toolchain to use (e.g., "stable", "nightly", "1.70.0"). If not provided, uses system default
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails or features are invalid
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -123,7 +127,7 @@ export declare function fromLocalByRustdoc(params: LlMsConfigRustdocByAllFeature
  * }
  * ```
  */
-export declare function fromLocalWithFeatures(manifestPath: string, noDefaultFeatures: boolean, features?: Array<string> | undefined | null, toolchain?: string | undefined | null): LlMsConfig | null
+export declare function fromLocalWithFeatures(manifestPath: string, noDefaultFeatures: boolean, features?: Array<string> | undefined | null, toolchain?: string | undefined | null, options?: ProcessOptions | undefined | null): LlMsConfig | null
 
 /**
  * Unified function to fetch crate documentation from online sources using either crate name or direct URL
@@ -133,6 +137,7 @@ export declare function fromLocalWithFeatures(manifestPath: string, noDefaultFea
  * @param params - A union type that accepts either:
  *   - LLMsConfigByCrate: { libName: string, version?: string }
  *   - LLMsConfigByUrl: { url: string }
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if failed
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -156,7 +161,7 @@ export declare function fromLocalWithFeatures(manifestPath: string, noDefaultFea
  * }
  * ```
  */
-export declare function fromOnline(params: LlMsConfigByCrate | LlMsConfigByUrl): Promise<LlMsConfig | null>
+export declare function fromOnline(params: LlMsConfigByCrate | LlMsConfigByUrl, options?: ProcessOptions | undefined | null): Promise<LlMsConfig | null>
 
 /**
  * Fetches Rust crate documentation from a direct URL to the JSON documentation
@@ -164,6 +169,7 @@ export declare function fromOnline(params: LlMsConfigByCrate | LlMsConfigByUrl):
  * giving you more control over the exact documentation source.
  *
  * @param url - The direct URL to the crate's JSON documentation index (must be a valid docs.rs JSON endpoint)
+ * @param options - Optional processing options, e.g. { includeUndocumented: true }
  * @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if the URL is unreachable or invalid
  * @throws Will return null instead of throwing errors for better TypeScript error handling
  * @example
@@ -182,17 +188,35 @@ export declare function fromOnline(params: LlMsConfigByCrate | LlMsConfigByUrl):
  * }
  * ```
  */
-export declare function fromUrl(url: string): Promise<LlMsConfig | null>
+export declare function fromUrl(url: string, options?: ProcessOptions | undefined | null): Promise<LlMsConfig | null>
 
 /**
  * Represents a full documentation session with complete content
  * @interface FullSessionItem
  * @property content - The complete documentation content as a string
- * @property link - The URL link to the original documentation source
+ * @property link - The URL link to the item's page on docs.rs
+ * @property sourceLink - Optional URL link to the item's source code, anchored at its first line
+ * @property undocumented - True if the item has no doc comment, in which case the content is its signature only
+ * @property mustUse - True if the item is marked `#[must_use]`
+ * @property nonExhaustive - True if the item is marked `#[non_exhaustive]`
+ * @property cfg - Conditions the item is gated on by `#[cfg]` or `#[doc(cfg)]` (e.g. `feature = "json"`)
+ * @property requiredFeatures - Cargo features that must be enabled for the item to exist (e.g. ["json"])
+ * @property deprecated - True if the item is marked `#[deprecated]`
+ * @property deprecatedSince - The version the item was deprecated in, if given
+ * @property deprecationNote - The reason given for the deprecation, if any
  */
 export interface FullSessionItem {
   content: string
   link: string
+  sourceLink?: string
+  undocumented: boolean
+  mustUse: boolean
+  nonExhaustive: boolean
+  cfg: Array<string>
+  requiredFeatures: Array<string>
+  deprecated: boolean
+  deprecatedSince?: string
+  deprecationNote?: string
 }
 
 /**
@@ -202,12 +226,14 @@ export interface FullSessionItem {
  * @property version - The version string of the crate
  * @property sessions - Array of session items with metadata for quick reference
  * @property fullSessions - Array of full session items with complete documentation content
+ * @property warnings - Non-fatal problems encountered while processing, such as items without a source span
  */
 export interface LlMsConfig {
   libName: string
   version: string
   sessions: Array<SessionItem>
   fullSessions: Array<FullSessionItem>
+  warnings: Array<ProcessWarning>
 }
 
 /**
@@ -256,15 +282,83 @@ export interface LlMsConfigRustdocByFeatures {
   features?: Array<string>
 }
 
+/**
+ * Options controlling which items of the crate are included in the generated documentation
+ * @interface ProcessOptions
+ * @property includeUndocumented - If true, public items without doc comments are included, using their signature as content. Defaults to false
+ * @property includesPrivate - If true, crate-visible items and items of private modules are included. Defaults to false
+ * @property includeDeprecated - If false, items marked #[deprecated] are left out. Defaults to true
+ * @property deprecatedItems - How items marked #[deprecated] are listed: "include", "optional" (in the Optional section of llms.txt) or "exclude". Takes precedence over includeDeprecated
+ * @property includeHidden - If true, items marked #[doc(hidden)] are included. Defaults to false
+ * @property includeBlanketImpls - If false, auto trait (Send, Sync, ...) and blanket impls are left out of the trait implementations listed under a type. Defaults to true
+ * @property includeKinds - Only include items of these kinds (e.g. ["struct", "trait", "function"])
+ * @property excludeKinds - Never include items of these kinds
+ * @property includePaths - Only include items whose path matches one of these glob patterns (e.g. ["clap::builder::**"])
+ * @property excludePaths - Never include items whose path matches one of these glob patterns
+ * @property maxItems - Maximum number of items listed after the crate root
+ * @property enabledFeatures - Only include items whose required cargo features are all in this list; items that are not feature-gated are always included
+ */
+export interface ProcessOptions {
+  includeUndocumented?: boolean
+  includesPrivate?: boolean
+  includeDeprecated?: boolean
+  deprecatedItems?: string
+  includeHidden?: boolean
+  includeBlanketImpls?: boolean
+  includeKinds?: Array<string>
+  excludeKinds?: Array<string>
+  includePaths?: Array<string>
+  excludePaths?: Array<string>
+  maxItems?: number
+  enabledFeatures?: Array<string>
+}
+
+/**
+ * A non-fatal problem encountered while processing a documentation item
+ * @interface ProcessWarning
+ * @property kind - The kind of problem (e.g. "missing_span", "non_utf8_path", "missing_link", "missing_title")
+ * @property itemId - The id of the item in the rustdoc index
+ * @property item - The title of the session generated for the item
+ * @property message - A human-readable description of the problem and the fallback used
+ */
+export interface ProcessWarning {
+  kind: string
+  itemId: number
+  item: string
+  message: string
+}
+
 /**
  * Represents a documentation session item with metadata
  * @interface SessionItem
  * @property title - The title of the documentation section
  * @property description - A brief description of the section content
- * @property link - The URL link to the full documentation
+ * @property link - The URL link to the item's page on docs.rs
+ * @property sourceLink - Optional URL link to the item's source code, anchored at its first line
+ * @property category - The item category used for llms.txt sections (e.g. "module", "struct", "function", "other")
+ * @property module - The path of the module the item is listed under, or an empty string for items without a path
+ * @property undocumented - True if the item has no doc comment (only when `includeUndocumented` is set)
+ * @property mustUse - True if the item is marked `#[must_use]`
+ * @property nonExhaustive - True if the item is marked `#[non_exhaustive]`
+ * @property cfg - Conditions the item is gated on by `#[cfg]` or `#[doc(cfg)]` (e.g. `feature = "json"`)
+ * @property requiredFeatures - Cargo features that must be enabled for the item to exist (e.g. ["json"])
+ * @property deprecated - True if the item is marked `#[deprecated]`
+ * @property deprecatedSince - The version the item was deprecated in, if given
+ * @property deprecationNote - The reason given for the deprecation, if any
  */
 export interface SessionItem {
   title: string
   description: string
   link: string
+  sourceLink?: string
+  category: string
+  module: string
+  undocumented: boolean
+  mustUse: boolean
+  nonExhaustive: boolean
+  cfg: Array<string>
+  requiredFeatures: Array<string>
+  deprecated: boolean
+  deprecatedSince?: string
+  deprecationNote?: string
 }
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crates_llms_txt::fetch_docs::OnlineDocs;
+use crates_llms_txt::options;
 use crates_llms_txt::CrateDocs;
 use napi::Either;
 use napi_derive::napi;
@@ -8,22 +10,76 @@ use napi_derive::napi;
 /// @interface SessionItem
 /// @property title - The title of the documentation section
 /// @property description - A brief description of the section content
-/// @property link - The URL link to the full documentation
+/// @property link - The URL link to the item's page on docs.rs
+/// @property sourceLink - Optional URL link to the item's source code, anchored at its first line
+/// @property category - The item category used for llms.txt sections (e.g. "module", "struct", "function", "other")
+/// @property module - The path of the module the item is listed under, or an empty string for items without a path
+/// @property undocumented - True if the item has no doc comment (only when `includeUndocumented` is set)
+/// @property mustUse - True if the item is marked `#[must_use]`
+/// @property nonExhaustive - True if the item is marked `#[non_exhaustive]`
+/// @property cfg - Conditions the item is gated on by `#[cfg]` or `#[doc(cfg)]` (e.g. `feature = "json"`)
+/// @property requiredFeatures - Cargo features that must be enabled for the item to exist (e.g. ["json"])
+/// @property deprecated - True if the item is marked `#[deprecated]`
+/// @property deprecatedSince - The version the item was deprecated in, if given
+/// @property deprecationNote - The reason given for the deprecation, if any
 #[napi(object)]
 pub struct SessionItem {
   pub title: String,
   pub description: String,
   pub link: String,
+  pub source_link: Option<String>,
+  pub category: String,
+  pub module: String,
+  pub undocumented: bool,
+  pub must_use: bool,
+  pub non_exhaustive: bool,
+  pub cfg: Vec<String>,
+  pub required_features: Vec<String>,
+  pub deprecated: bool,
+  pub deprecated_since: Option<String>,
+  pub deprecation_note: Option<String>,
 }
 
 /// Represents a full documentation session with complete content
 /// @interface FullSessionItem
 /// @property content - The complete documentation content as a string
-/// @property link - The URL link to the original documentation source
+/// @property link - The URL link to the item's page on docs.rs
+/// @property sourceLink - Optional URL link to the item's source code, anchored at its first line
+/// @property undocumented - True if the item has no doc comment, in which case the content is its signature only
+/// @property mustUse - True if the item is marked `#[must_use]`
+/// @property nonExhaustive - True if the item is marked `#[non_exhaustive]`
+/// @property cfg - Conditions the item is gated on by `#[cfg]` or `#[doc(cfg)]` (e.g. `feature = "json"`)
+/// @property requiredFeatures - Cargo features that must be enabled for the item to exist (e.g. ["json"])
+/// @property deprecated - True if the item is marked `#[deprecated]`
+/// @property deprecatedSince - The version the item was deprecated in, if given
+/// @property deprecationNote - The reason given for the deprecation, if any
 #[napi(object)]
 pub struct FullSessionItem {
   pub content: String,
   pub link: String,
+  pub source_link: Option<String>,
+  pub undocumented: bool,
+  pub must_use: bool,
+  pub non_exhaustive: bool,
+  pub cfg: Vec<String>,
+  pub required_features: Vec<String>,
+  pub deprecated: bool,
+  pub deprecated_since: Option<String>,
+  pub deprecation_note: Option<String>,
+}
+
+/// A non-fatal problem encountered while processing a documentation item
+/// @interface ProcessWarning
+/// @property kind - The kind of problem (e.g. "missing_span", "non_utf8_path", "missing_link", "missing_title")
+/// @property itemId - The id of the item in the rustdoc index
+/// @property item - The title of the session generated for the item
+/// @property message - A human-readable description of the problem and the fallback used
+#[napi(object)]
+pub struct ProcessWarning {
+  pub kind: String,
+  pub item_id: u32,
+  pub item: String,
+  pub message: String,
 }
 
 /// Main configuration object containing all documentation data for LLM consumption
@@ -32,12 +88,14 @@ pub struct FullSessionItem {
 /// @property version - The version string of the crate
 /// @property sessions - Array of session items with metadata for quick reference
 /// @property fullSessions - Array of full session items with complete documentation content
+/// @property warnings - Non-fatal problems encountered while processing, such as items without a source span
 #[napi(object)]
 pub struct LLMsConfig {
   pub lib_name: String,
   pub version: String,
   pub sessions: Vec<SessionItem>,
   pub full_sessions: Vec<FullSessionItem>,
+  pub warnings: Vec<ProcessWarning>,
 }
 
 /// Configuration for fetching documentation by crate name
@@ -82,6 +140,84 @@ pub struct LLMsConfigRustdocByFeatures {
   pub features: Option<Vec<String>>,
 }
 
+/// Options controlling which items of the crate are included in the generated documentation
+/// @interface ProcessOptions
+/// @property includeUndocumented - If true, public items without doc comments are included, using their signature as content. Defaults to false
+/// @property includesPrivate - If true, crate-visible items and items of private modules are included. Defaults to false
+/// @property includeDeprecated - If false, items marked #[deprecated] are left out. Defaults to true
+/// @property deprecatedItems - How items marked #[deprecated] are listed: "include", "optional" (in the Optional section of llms.txt) or "exclude". Takes precedence over includeDeprecated
+/// @property includeHidden - If true, items marked #[doc(hidden)] are included. Defaults to false
+/// @property includeBlanketImpls - If false, auto trait (Send, Sync, ...) and blanket impls are left out of the trait implementations listed under a type. Defaults to true
+/// @property includeKinds - Only include items of these kinds (e.g. ["struct", "trait", "function"])
+/// @property excludeKinds - Never include items of these kinds
+/// @property includePaths - Only include items whose path matches one of these glob patterns (e.g. ["clap::builder::**"])
+/// @property excludePaths - Never include items whose path matches one of these glob patterns
+/// @property maxItems - Maximum number of items listed after the crate root
+/// @property enabledFeatures - Only include items whose required cargo features are all in this list; items that are not feature-gated are always included
+#[napi(object)]
+pub struct ProcessOptions {
+  pub include_undocumented: Option<bool>,
+  pub includes_private: Option<bool>,
+  pub include_deprecated: Option<bool>,
+  pub deprecated_items: Option<String>,
+  pub include_hidden: Option<bool>,
+  pub include_blanket_impls: Option<bool>,
+  pub include_kinds: Option<Vec<String>>,
+  pub exclude_kinds: Option<Vec<String>>,
+  pub include_paths: Option<Vec<String>>,
+  pub exclude_paths: Option<Vec<String>>,
+  pub max_items: Option<u32>,
+  pub enabled_features: Option<Vec<String>>,
+}
+
+/// Internal utility function to convert the optional NAPI processing options into the library's ProcessOptions
+/// Missing options, or missing fields, fall back to the library defaults
+/// @param options - The options received from TypeScript
+/// @returns options::ProcessOptions | null - The options understood by the Rust library, or null if an item kind or deprecated items mode is unknown
+fn convert_process_options(
+  options: Option<ProcessOptions>,
+) -> Option<options::ProcessOptions> {
+  let Some(options) = options else {
+    return Some(options::ProcessOptions::default());
+  };
+  let parse_kinds = |kinds: Option<Vec<String>>| {
+    kinds
+      .unwrap_or_default()
+      .iter()
+      .map(|kind| options::parse_item_kind(kind))
+      .collect::<Option<Vec<_>>>()
+  };
+
+  let defaults = options::ProcessOptions::default();
+  Some(options::ProcessOptions {
+    include_undocumented: options
+      .include_undocumented
+      .unwrap_or(defaults.include_undocumented),
+    includes_private: options
+      .includes_private
+      .unwrap_or(defaults.includes_private),
+    deprecated_items: match (
+      options.deprecated_items,
+      options.include_deprecated,
+    ) {
+      (Some(mode), _) => options::DeprecatedItems::parse(&mode)?,
+      (None, Some(true)) => options::DeprecatedItems::Include,
+      (None, Some(false)) => options::DeprecatedItems::Exclude,
+      (None, None) => defaults.deprecated_items,
+    },
+    include_hidden: options.include_hidden.unwrap_or(defaults.include_hidden),
+    include_blanket_impls: options
+      .include_blanket_impls
+      .unwrap_or(defaults.include_blanket_impls),
+    include_kinds: parse_kinds(options.include_kinds)?,
+    exclude_kinds: parse_kinds(options.exclude_kinds)?,
+    include_paths: options.include_paths.unwrap_or_default(),
+    exclude_paths: options.exclude_paths.unwrap_or_default(),
+    max_items: options.max_items.map(|max| max as usize),
+    enabled_features: options.enabled_features,
+  })
+}
+
 /// Internal utility function to convert CrateDocs to LLMsConfig format for NAPI compatibility
 /// This function transforms the internal Rust documentation structure into the TypeScript-friendly format
 /// @param docs - The internal CrateDocs structure from the Rust library
@@ -95,6 +231,22 @@ fn convert_crate_docs_to_llms_config(docs: CrateDocs) -> LLMsConfig {
       title: session.title,
       description: session.description,
       link: session.link,
+      source_link: session.source_link,
+      category: session.category.as_str().to_string(),
+      module: session.module,
+      undocumented: session.undocumented,
+      must_use: session.attributes.must_use,
+      non_exhaustive: session.attributes.non_exhaustive,
+      cfg: session.attributes.cfg,
+      required_features: session.required_features,
+      deprecated: session.deprecation.is_some(),
+      deprecated_since: session
+        .deprecation
+        .as_ref()
+        .and_then(|deprecation| deprecation.since.clone()),
+      deprecation_note: session
+        .deprecation
+        .and_then(|deprecation| deprecation.note),
     })
     .collect();
 
@@ -105,6 +257,32 @@ fn convert_crate_docs_to_llms_config(docs: CrateDocs) -> LLMsConfig {
     .map(|full_session| FullSessionItem {
       content: full_session.content,
       link: full_session.link,
+      source_link: full_session.source_link,
+      undocumented: full_session.undocumented,
+      must_use: full_session.attributes.must_use,
+      non_exhaustive: full_session.attributes.non_exhaustive,
+      cfg: full_session.attributes.cfg,
+      required_features: full_session.required_features,
+      deprecated: full_session.deprecation.is_some(),
+      deprecated_since: full_session
+        .deprecation
+        .as_ref()
+        .and_then(|deprecation| deprecation.since.clone()),
+      deprecation_note: full_session
+        .deprecation
+        .and_then(|deprecation| deprecation.note),
+    })
+    .collect();
+
+  // Convert warnings
+  let warnings = docs
+    .warnings
+    .into_iter()
+    .map(|warning| ProcessWarning {
+      kind: warning.kind.as_str().to_string(),
+      item_id: warning.item_id,
+      item: warning.item,
+      message: warning.message,
     })
     .collect();
 
@@ -113,6 +291,7 @@ fn convert_crate_docs_to_llms_config(docs: CrateDocs) -> LLMsConfig {
     version: docs.version,
     sessions,
     full_sessions,
+    warnings,
   }
 }
 
@@ -123,6 +302,7 @@ fn convert_crate_docs_to_llms_config(docs: CrateDocs) -> LLMsConfig {
 ///
 /// @param libName - The name of the crate as it appears on crates.io (e.g., "clap", "serde", "tokio")
 /// @param version - Optional version string. If not provided or null, the latest version will be fetched
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if the crate is not found or an error occurs
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -143,8 +323,11 @@ fn convert_crate_docs_to_llms_config(docs: CrateDocs) -> LLMsConfig {
 pub async fn from_crate_name(
   lib_name: String,
   version: Option<String>,
+  options: Option<ProcessOptions>,
 ) -> Option<LLMsConfig> {
-  match CrateDocs::from_online(&lib_name, version).await {
+  let options = convert_process_options(options)?;
+  let online = OnlineDocs::builder().build().ok()?;
+  match CrateDocs::from_online(&lib_name, version, &online, &options).await {
     Ok(docs) => Some(convert_crate_docs_to_llms_config(docs)),
     Err(_) => None,
   }
@@ -156,6 +339,7 @@ pub async fn from_crate_name(
 /// giving you more control over the exact documentation source.
 ///
 /// @param url - The direct URL to the crate's JSON documentation index (must be a valid docs.rs JSON endpoint)
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if the URL is unreachable or invalid
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -173,8 +357,13 @@ pub async fn from_crate_name(
 ///   console.log(`Documentation contains ${config.fullSessions.length} full sections`);
 /// }
 /// ```
-pub async fn from_url(url: String) -> Option<LLMsConfig> {
-  match CrateDocs::from_url(&url).await {
+pub async fn from_url(
+  url: String,
+  options: Option<ProcessOptions>,
+) -> Option<LLMsConfig> {
+  let options = convert_process_options(options)?;
+  let online = OnlineDocs::builder().build().ok()?;
+  match CrateDocs::from_url(&url, &online, &options).await {
     Ok(docs) => Some(convert_crate_docs_to_llms_config(docs)),
     Err(_) => None,
   }
@@ -188,6 +377,7 @@ pub async fn from_url(url: String) -> Option<LLMsConfig> {
 /// @param params - A union type that accepts either:
 ///   - LLMsConfigByCrate: { libName: string, version?: string }
 ///   - LLMsConfigByUrl: { url: string }
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns Promise<LLMsConfig | null> - A promise that resolves to the documentation configuration, or null if failed
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -212,10 +402,13 @@ pub async fn from_url(url: String) -> Option<LLMsConfig> {
 /// ```
 pub async fn from_online(
   params: Either<LLMsConfigByCrate, LLMsConfigByUrl>,
+  options: Option<ProcessOptions>,
 ) -> Option<LLMsConfig> {
   match params {
-    Either::A(params) => from_crate_name(params.lib_name, params.version).await,
-    Either::B(params) => from_url(params.url).await,
+    Either::A(params) => {
+      from_crate_name(params.lib_name, params.version, options).await
+    }
+    Either::B(params) => from_url(params.url, options).await,
   }
 }
 
@@ -226,6 +419,7 @@ pub async fn from_online(
 ///
 /// @param manifestPath - Absolute or relative path to the Cargo.toml file of the target crate
 /// @param toolchain - Optional Rust toolchain to use (e.g., "stable", "nightly", "1.70.0"). If not provided, uses system default
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails or toolchain is unavailable
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -247,9 +441,11 @@ pub async fn from_online(
 pub fn from_local(
   manifest_path: String,
   toolchain: Option<String>,
+  options: Option<ProcessOptions>,
 ) -> Option<LLMsConfig> {
   let manifest_path = PathBuf::from(manifest_path);
-  match CrateDocs::from_local(manifest_path, toolchain) {
+  let options = convert_process_options(options)?;
+  match CrateDocs::from_local(manifest_path, toolchain, &options) {
     Ok(docs) => Some(convert_crate_docs_to_llms_config(docs)),
     Err(_) => None,
   }
@@ -264,6 +460,7 @@ pub fn from_local(
 /// @param noDefaultFeatures - If true, disables all default features of the crate (equivalent to --no-default-features)
 /// @param features - Optional array of specific feature names to enable (e.g., ["async", "serde", "tokio"])
 /// @param toolchain - Optional Rust toolchain to use (e.g., "stable", "nightly", "1.70.0"). If not provided, uses system default
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails or features are invalid
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -288,13 +485,16 @@ pub fn from_local_with_features(
   no_default_features: bool,
   features: Option<Vec<String>>,
   toolchain: Option<String>,
+  options: Option<ProcessOptions>,
 ) -> Option<LLMsConfig> {
   let manifest_path = PathBuf::from(manifest_path);
+  let options = convert_process_options(options)?;
   match CrateDocs::from_local_with_features(
     manifest_path,
     no_default_features,
     features,
     toolchain,
+    &options,
   ) {
     Ok(docs) => Some(convert_crate_docs_to_llms_config(docs)),
     Err(_) => None,
@@ -309,6 +509,7 @@ pub fn from_local_with_features(
 /// @param params - A union type that accepts either:
 ///   - LLMsConfigRustdocByAllFeatures: { toolchain?: string, manifestPath: string }
 ///   - LLMsConfigRustdocByFeatures: { toolchain?: string, manifestPath: string, noDefaultFeatures: boolean, features?: string[] }
+/// @param options - Optional processing options, e.g. { includeUndocumented: true }
 /// @returns LLMsConfig | null - The generated documentation configuration, or null if compilation fails
 /// @throws Will return null instead of throwing errors for better TypeScript error handling
 /// @example
@@ -339,14 +540,18 @@ pub fn from_local_with_features(
 /// ```
 pub fn from_local_by_rustdoc(
   params: Either<LLMsConfigRustdocByAllFeatures, LLMsConfigRustdocByFeatures>,
+  options: Option<ProcessOptions>,
 ) -> Option<LLMsConfig> {
   match params {
-    Either::A(params) => from_local(params.manifest_path, params.toolchain),
+    Either::A(params) => {
+      from_local(params.manifest_path, params.toolchain, options)
+    }
     Either::B(params) => from_local_with_features(
       params.manifest_path,
       params.no_default_features,
       params.features,
       params.toolchain,
+      options,
     ),
   }
 }
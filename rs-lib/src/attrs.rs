@@ -0,0 +1,446 @@
+//! # Attribute Parsing
+//!
+//! `Item::attrs` holds attributes in their source form, e.g. `#[must_use]` or
+//! `#[doc(cfg(feature = "json"))]`. This module parses them into a typed
+//! form and summarizes the ones relevant to generated documentation:
+//! `#[doc(hidden)]`, `#[must_use]`, `#[non_exhaustive]` and the
+//! `#[cfg(...)]`/`#[doc(cfg(...))]` conditions an item is gated on.
+
+use rustdoc_types::{AttributeRepr, ReprKind};
+use serde::{Deserialize, Serialize};
+
+/// A parsed attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attr {
+  /// `#[doc(hidden)]`
+  DocHidden,
+  /// `#[must_use]` or `#[must_use = "reason"]`
+  MustUse {
+    /// The reason given with the attribute, if any
+    reason: Option<String>,
+  },
+  /// `#[non_exhaustive]`
+  NonExhaustive,
+  /// `#[cfg(predicate)]`, holding the predicate, e.g. `feature = "json"`
+  Cfg(String),
+  /// `#[doc(cfg(predicate))]`, holding the predicate
+  DocCfg(String),
+  /// Any other attribute, in source form
+  Other(String),
+}
+
+/// Attributes of an item that are recorded on its sessions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemAttributes {
+  /// Whether the item is marked `#[must_use]`
+  #[serde(default)]
+  pub must_use: bool,
+  /// Whether the item is marked `#[non_exhaustive]`
+  #[serde(default)]
+  pub non_exhaustive: bool,
+  /// Conditions the item is gated on, from `#[cfg]` and `#[doc(cfg)]`,
+  /// e.g. `feature = "json"`. Duplicates are removed.
+  #[serde(default)]
+  pub cfg: Vec<String>,
+}
+
+impl ItemAttributes {
+  /// Summarize parsed attributes.
+  pub fn from_attrs(attrs: &[Attr]) -> Self {
+    let mut summary = ItemAttributes::default();
+    for attr in attrs {
+      match attr {
+        Attr::MustUse { .. } => summary.must_use = true,
+        Attr::NonExhaustive => summary.non_exhaustive = true,
+        Attr::Cfg(predicate) | Attr::DocCfg(predicate) => {
+          if !summary.cfg.contains(predicate) {
+            summary.cfg.push(predicate.clone());
+          }
+        }
+        Attr::DocHidden | Attr::Other(_) => {}
+      }
+    }
+    summary
+  }
+
+  /// Attribute lines shown above the signature of the item, as rustdoc does.
+  pub fn signature_prefix(&self) -> String {
+    let mut prefix = String::new();
+    if self.must_use {
+      prefix.push_str("#[must_use]\n");
+    }
+    if self.non_exhaustive {
+      prefix.push_str("#[non_exhaustive]\n");
+    }
+    prefix
+  }
+}
+
+/// Parse the attributes of an item.
+///
+/// # Arguments
+///
+/// * `attrs` - The attributes, as stored in `Item::attrs`
+///
+/// # Returns
+///
+/// * `Vec<Attr>` - The parsed attributes, in order
+pub fn parse_attrs(attrs: &[String]) -> Vec<Attr> {
+  attrs.iter().map(|attr| parse_attr(attr)).collect()
+}
+
+/// Whether the attributes contain `#[doc(hidden)]`.
+pub fn is_hidden(attrs: &[Attr]) -> bool {
+  attrs.contains(&Attr::DocHidden)
+}
+
+/// Parse a single attribute from its source form.
+///
+/// Attributes serialized as JSON by older versions of this crate are
+/// converted to their source form first.
+///
+/// # Examples
+///
+/// ```
+/// use crates_llms_txt::attrs::{parse_attr, Attr};
+///
+/// assert_eq!(parse_attr("#[doc(hidden)]"), Attr::DocHidden);
+/// assert_eq!(
+///   parse_attr("#[doc(cfg(feature = \"json\"))]"),
+///   Attr::DocCfg("feature = \"json\"".to_string())
+/// );
+/// ```
+pub fn parse_attr(attr: &str) -> Attr {
+  let attr = attr.trim();
+  if attr.starts_with('{') || attr.starts_with('"') {
+    if let Ok(parsed) = serde_json::from_str::<rustdoc_types::Attribute>(attr) {
+      return parse_attr(&to_source(&parsed));
+    }
+  }
+
+  let Some(body) = attr
+    .strip_prefix("#[")
+    .and_then(|body| body.strip_suffix(']'))
+    .map(str::trim)
+  else {
+    return Attr::Other(attr.to_string());
+  };
+
+  if let Some(predicate) = call_args(body, "cfg").or(call_args(body, "<cfg>")) {
+    return Attr::Cfg(normalize(predicate));
+  }
+  if let Some(doc) = call_args(body, "doc") {
+    if doc == "hidden" {
+      return Attr::DocHidden;
+    }
+    if let Some(predicate) = call_args(doc, "cfg") {
+      return Attr::DocCfg(normalize(predicate));
+    }
+  }
+  if body == "non_exhaustive" {
+    return Attr::NonExhaustive;
+  }
+  if body == "must_use" {
+    return Attr::MustUse { reason: None };
+  }
+  if let Some(reason) = body
+    .strip_prefix("must_use")
+    .map(str::trim_start)
+    .and_then(|rest| rest.strip_prefix('='))
+  {
+    let reason = reason.trim().trim_matches('"').to_string();
+    return Attr::MustUse {
+      reason: Some(reason),
+    };
+  }
+
+  Attr::Other(attr.to_string())
+}
+
+/// Extract the cargo features required by a set of cfg predicates.
+///
+/// A feature is required when it appears as `feature = "name"` at the top
+/// level of a predicate or inside `all(...)`. Features under `any(...)` or
+/// `not(...)` are alternatives or exclusions and are not reported.
+///
+/// # Arguments
+///
+/// * `cfg` - The predicates, e.g. from `ItemAttributes::cfg`
+///
+/// # Returns
+///
+/// * `Vec<String>` - The required feature names, sorted and deduplicated
+///
+/// # Examples
+///
+/// ```
+/// use crates_llms_txt::attrs::required_features;
+///
+/// let cfg = vec![
+///   "all(feature = \"json\", feature = \"async\")".to_string(),
+///   "any(unix, feature = \"tls\")".to_string(),
+/// ];
+/// assert_eq!(required_features(&cfg), vec!["async", "json"]);
+/// ```
+pub fn required_features(cfg: &[String]) -> Vec<String> {
+  fn collect(predicate: &str, features: &mut Vec<String>) {
+    if let Some(inner) = call_args(predicate, "all") {
+      for part in split_top_level(inner) {
+        collect(part, features);
+      }
+      return;
+    }
+    let Some((key, value)) = predicate.split_once('=') else {
+      return;
+    };
+    if key.trim() == "feature" {
+      let value = value.trim();
+      if let Some(name) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+      {
+        features.push(name.to_string());
+      }
+    }
+  }
+
+  let mut features = Vec::new();
+  for predicate in cfg {
+    collect(predicate.trim(), &mut features);
+  }
+  features.sort();
+  features.dedup();
+  features
+}
+
+/// Render a rustdoc attribute in its source form.
+///
+/// # Arguments
+///
+/// * `attr` - The attribute, from `rustdoc_types::Item::attrs`
+///
+/// # Returns
+///
+/// * `String` - The attribute as it would be written in source code
+pub fn to_source(attr: &rustdoc_types::Attribute) -> String {
+  use rustdoc_types::Attribute;
+
+  match attr {
+    Attribute::NonExhaustive => "#[non_exhaustive]".to_string(),
+    Attribute::MustUse { reason: None } => "#[must_use]".to_string(),
+    Attribute::MustUse {
+      reason: Some(reason),
+    } => format!("#[must_use = {reason:?}]"),
+    Attribute::MacroExport => "#[macro_export]".to_string(),
+    Attribute::ExportName(name) => format!("#[export_name = {name:?}]"),
+    Attribute::LinkSection(name) => format!("#[link_section = {name:?}]"),
+    Attribute::AutomaticallyDerived => "#[automatically_derived]".to_string(),
+    Attribute::Repr(repr) => format!("#[repr({})]", repr_args(repr)),
+    Attribute::NoMangle => "#[no_mangle]".to_string(),
+    Attribute::TargetFeature { enable } => format!(
+      "#[target_feature({})]",
+      enable
+        .iter()
+        .map(|feature| format!("enable = {feature:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+    Attribute::Other(source) => source.clone(),
+  }
+}
+
+/// Arguments of a `#[repr(...)]` attribute, e.g. `C, align(8)`.
+fn repr_args(repr: &AttributeRepr) -> String {
+  let mut args = Vec::new();
+  match repr.kind {
+    ReprKind::Rust => {}
+    ReprKind::C => args.push("C".to_string()),
+    ReprKind::Transparent => args.push("transparent".to_string()),
+    ReprKind::Simd => args.push("simd".to_string()),
+  }
+  if let Some(int) = &repr.int {
+    args.push(int.clone());
+  }
+  if let Some(align) = repr.align {
+    args.push(format!("align({align})"));
+  }
+  if let Some(packed) = repr.packed {
+    args.push(format!("packed({packed})"));
+  }
+  if args.is_empty() {
+    args.push("Rust".to_string());
+  }
+  args.join(", ")
+}
+
+/// Return the arguments of `name(...)` if `body` is exactly such a call.
+fn call_args<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+  body
+    .strip_prefix(name)?
+    .trim_start()
+    .strip_prefix('(')?
+    .strip_suffix(')')
+    .map(str::trim)
+}
+
+/// Split a comma-separated list of predicates, ignoring commas nested in
+/// parentheses or string literals.
+fn split_top_level(list: &str) -> Vec<&str> {
+  let mut parts = Vec::new();
+  let (mut depth, mut quoted, mut start) = (0usize, false, 0);
+  for (index, byte) in list.bytes().enumerate() {
+    match byte {
+      b'"' => quoted = !quoted,
+      b'(' if !quoted => depth += 1,
+      b')' if !quoted => depth = depth.saturating_sub(1),
+      b',' if !quoted && depth == 0 => {
+        parts.push(list[start..index].trim());
+        start = index + 1;
+      }
+      _ => {}
+    }
+  }
+  parts.push(list[start..].trim());
+  parts.retain(|part| !part.is_empty());
+  parts
+}
+
+/// Collapse whitespace in a cfg predicate.
+fn normalize(predicate: &str) -> String {
+  predicate.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::tests::{process, session, titles};
+
+  #[test]
+  fn test_parse_attr_forms() {
+    assert_eq!(parse_attr("#[non_exhaustive]"), Attr::NonExhaustive);
+    assert_eq!(
+      parse_attr("#[must_use = \"iterators are lazy\"]"),
+      Attr::MustUse {
+        reason: Some("iterators are lazy".to_string())
+      }
+    );
+    assert_eq!(
+      parse_attr("#[<cfg>(feature = \"json\")]"),
+      Attr::Cfg("feature = \"json\"".to_string())
+    );
+    assert_eq!(
+      parse_attr("#[cfg(any(unix,  windows))]"),
+      Attr::Cfg("any(unix, windows)".to_string())
+    );
+    // Serialized `rustdoc_types::Attribute` values
+    assert_eq!(
+      parse_attr(r#"{"must_use":{"reason":null}}"#),
+      Attr::MustUse { reason: None }
+    );
+    assert_eq!(
+      parse_attr(r##"{"other":"#[doc(hidden)]"}"##),
+      Attr::DocHidden
+    );
+    assert_eq!(
+      parse_attr("#[<cfg_attr>(docsrs, doc(cfg(feature = \"json\")))]"),
+      Attr::Other(
+        "#[<cfg_attr>(docsrs, doc(cfg(feature = \"json\")))]".to_string()
+      )
+    );
+  }
+
+  #[test]
+  fn test_item_attributes_summary() {
+    let attrs = parse_attrs(&[
+      "#[must_use]".to_string(),
+      "#[<cfg>(feature = \"json\")]".to_string(),
+      "#[doc(cfg(feature = \"json\"))]".to_string(),
+      "#[inline]".to_string(),
+    ]);
+    let summary = ItemAttributes::from_attrs(&attrs);
+
+    assert!(summary.must_use);
+    assert!(!summary.non_exhaustive);
+    assert_eq!(summary.cfg, vec!["feature = \"json\""]);
+    assert_eq!(summary.signature_prefix(), "#[must_use]\n");
+    assert!(!is_hidden(&attrs));
+  }
+
+  #[test]
+  fn test_required_features() {
+    let cfg = [
+      "feature = \"json\"",
+      "all(unix, feature = \"async\", all(feature = \"json\"))",
+      "not(feature = \"std\")",
+      "any(feature = \"a\", feature = \"b\")",
+    ]
+    .map(String::from);
+
+    assert_eq!(required_features(&cfg), vec!["async", "json"]);
+    assert!(required_features(&["docsrs".to_string()]).is_empty());
+  }
+
+  #[test]
+  fn test_to_source() {
+    use rustdoc_types::Attribute;
+
+    let repr = Attribute::Repr(AttributeRepr {
+      kind: ReprKind::C,
+      align: Some(8),
+      packed: None,
+      int: None,
+    });
+    assert_eq!(to_source(&repr), "#[repr(C, align(8))]");
+    assert_eq!(
+      to_source(&Attribute::MustUse {
+        reason: Some("use it".to_string())
+      }),
+      "#[must_use = \"use it\"]"
+    );
+  }
+
+  #[test]
+  fn test_process_docs_attributes() {
+    let docs = process(&ProcessOptions::default());
+
+    let (count, count_full) = session(&docs, "demo::count");
+    assert!(count.attributes.must_use);
+    assert!(count_full
+      .content
+      .starts_with("```rust\n#[must_use]\npub fn"));
+
+    let (token, _) = session(&docs, "demo::parser::Token");
+    assert!(token.attributes.non_exhaustive);
+    assert!(!token.attributes.must_use);
+
+    let (to_json, to_json_full) = session(&docs, "demo::to_json");
+    assert_eq!(to_json.attributes.cfg, vec!["feature = \"json\""]);
+    assert_eq!(to_json_full.attributes, to_json.attributes);
+  }
+
+  #[test]
+  fn test_process_docs_required_features() {
+    let docs = process(&ProcessOptions::default());
+    let (to_json, to_json_full) = session(&docs, "demo::to_json");
+    assert_eq!(to_json.required_features, vec!["json"]);
+    assert_eq!(to_json_full.required_features, vec!["json"]);
+    assert!(docs
+      .to_llms_txt()
+      .lines()
+      .any(|line| line.starts_with("- [demo::to_json](")
+        && line.ends_with(" Requires feature `json`.")));
+
+    let without_json = process(
+      &ProcessOptions::builder()
+        .enabled_features(Vec::<String>::new())
+        .build(),
+    );
+    assert!(!titles(&without_json).contains(&"demo::to_json"));
+    assert!(titles(&without_json).contains(&"demo::parse"));
+
+    let with_json =
+      process(&ProcessOptions::builder().enabled_features(["json"]).build());
+    assert!(titles(&with_json).contains(&"demo::to_json"));
+  }
+}
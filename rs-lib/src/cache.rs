@@ -0,0 +1,349 @@
+//! # Documentation Cache
+//!
+//! Downloading the rustdoc JSON of a crate from docs.rs is the slow part of
+//! `CrateDocs::from_online`. This module keeps the downloaded documents on
+//! disk so that regenerating documentation for the same crates does not hit
+//! docs.rs again.
+//!
+//! Documents are keyed by crate name and resolved version and stored as the
+//! zstd payload served by docs.rs:
+//!
+//! ```text
+//! <dir>/<crate>/<version>.json.zst
+//! <dir>/<crate>/latest.json
+//! ```
+//!
+//! A released version never changes, so its document is used as is. `latest`
+//! is an alias recording the version it resolved to, with the `ETag` and
+//! `Last-Modified` validators of the response, which are sent back to docs.rs
+//! to revalidate the alias. In offline mode the network is never used.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::{Duration, SystemTime};
+//!
+//! use crates_llms_txt::cache::DocsCache;
+//!
+//! let cache = DocsCache::new("./.docs-cache");
+//! for entry in cache.entries()? {
+//!   println!("{} {} ({} bytes)", entry.lib_name, entry.version, entry.size);
+//! }
+//!
+//! // Drop documents not refreshed for a month
+//! let month_ago = SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60);
+//! cache.prune(|entry| entry.fetched_at < month_ago)?;
+//! # Ok::<(), crates_llms_txt::error::Error>(())
+//! ```
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// Extension of cached documents
+const DOCUMENT_EXTENSION: &str = ".json.zst";
+
+/// File name of the `latest` alias of a crate
+const LATEST_FILE_NAME: &str = "latest.json";
+
+/// An on-disk cache of rustdoc JSON documents downloaded from docs.rs.
+#[derive(Debug, Clone)]
+pub struct DocsCache {
+  dir: PathBuf,
+  offline: bool,
+}
+
+/// A cached document, as listed by `DocsCache::entries`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+  /// Name of the crate
+  pub lib_name: String,
+  /// Resolved version of the crate, e.g. `1.0.219`
+  pub version: String,
+  /// Size of the zstd payload in bytes
+  pub size: u64,
+  /// When the document was downloaded
+  pub fetched_at: SystemTime,
+  /// Path of the zstd payload
+  pub path: PathBuf,
+}
+
+/// The version `latest` resolved to and the validators of the response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LatestAlias {
+  /// The resolved version
+  pub version: String,
+  /// `ETag` of the response, sent back as `If-None-Match`
+  pub etag: Option<String>,
+  /// `Last-Modified` of the response, sent back as `If-Modified-Since`
+  pub last_modified: Option<String>,
+}
+
+impl DocsCache {
+  /// Create a cache stored in `dir`, which is created on first use.
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    DocsCache {
+      dir: dir.into(),
+      offline: false,
+    }
+  }
+
+  /// Only read from the cache, never from the network. Documents missing
+  /// from the cache are reported as errors.
+  pub fn offline(mut self, offline: bool) -> Self {
+    self.offline = offline;
+    self
+  }
+
+  /// The directory the cache is stored in.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Whether the cache is in offline mode.
+  pub fn is_offline(&self) -> bool {
+    self.offline
+  }
+
+  /// List the cached documents, sorted by crate name and version, with
+  /// versions in semver order, e.g. `0.9.0` before `0.10.0`.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Io` - If the cache directory cannot be read
+  pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+    let mut entries = Vec::new();
+    let crates = match fs::read_dir(&self.dir) {
+      Ok(crates) => crates,
+      Err(err) if err.kind() == ErrorKind::NotFound => return Ok(entries),
+      Err(err) => return Err(Error::Io(err)),
+    };
+    for crate_dir in crates {
+      let crate_dir = crate_dir?;
+      if !crate_dir.file_type()?.is_dir() {
+        continue;
+      }
+      let lib_name = crate_dir.file_name().to_string_lossy().into_owned();
+      for file in fs::read_dir(crate_dir.path())? {
+        let file = file?;
+        let file_name = file.file_name().to_string_lossy().into_owned();
+        let Some(version) = file_name.strip_suffix(DOCUMENT_EXTENSION) else {
+          continue;
+        };
+        let metadata = file.metadata()?;
+        entries.push(CacheEntry {
+          lib_name: lib_name.clone(),
+          version: version.to_string(),
+          size: metadata.len(),
+          fetched_at: metadata.modified()?,
+          path: file.path(),
+        });
+      }
+    }
+    entries.sort_by(|a, b| {
+      a.lib_name
+        .cmp(&b.lib_name)
+        .then_with(|| compare_versions(&a.version, &b.version))
+    });
+    Ok(entries)
+  }
+
+  /// Remove the cached documents matching `remove`, along with the `latest`
+  /// aliases resolving to them.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Vec<CacheEntry>>` - The removed entries
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Io` - If the cache directory cannot be read or a file cannot
+  ///   be removed
+  pub fn prune(
+    &self,
+    remove: impl Fn(&CacheEntry) -> bool,
+  ) -> Result<Vec<CacheEntry>> {
+    let mut removed = Vec::new();
+    for entry in self.entries()? {
+      if !remove(&entry) {
+        continue;
+      }
+      fs::remove_file(&entry.path)?;
+      if self
+        .latest(&entry.lib_name)?
+        .is_some_and(|alias| alias.version == entry.version)
+      {
+        fs::remove_file(
+          self.crate_dir(&entry.lib_name)?.join(LATEST_FILE_NAME),
+        )?;
+      }
+      removed.push(entry);
+    }
+    Ok(removed)
+  }
+
+  /// Read a cached document, decompressed.
+  pub(crate) fn load(
+    &self,
+    lib_name: &str,
+    version: &str,
+  ) -> Result<Option<Vec<u8>>> {
+    match fs::read(self.document_path(lib_name, version)?) {
+      Ok(payload) => Ok(Some(zstd::decode_all(payload.as_slice())?)),
+      Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+      Err(err) => Err(Error::Io(err)),
+    }
+  }
+
+  /// Store the zstd payload of a document.
+  pub(crate) fn store(
+    &self,
+    lib_name: &str,
+    version: &str,
+    payload: &[u8],
+  ) -> Result<()> {
+    let path = self.document_path(lib_name, version)?;
+    fs::create_dir_all(self.crate_dir(lib_name)?)?;
+    // Write to a temporary file first so that readers never see a partial
+    // document
+    let partial = path.with_extension("zst.partial");
+    fs::write(&partial, payload)?;
+    fs::rename(&partial, &path)?;
+    Ok(())
+  }
+
+  /// Read the `latest` alias of a crate.
+  pub(crate) fn latest(&self, lib_name: &str) -> Result<Option<LatestAlias>> {
+    let path = self.crate_dir(lib_name)?.join(LATEST_FILE_NAME);
+    match fs::read(path) {
+      Ok(json) => Ok(Some(serde_json::from_slice(&json)?)),
+      Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+      Err(err) => Err(Error::Io(err)),
+    }
+  }
+
+  /// Record the version `latest` resolves to.
+  pub(crate) fn set_latest(
+    &self,
+    lib_name: &str,
+    alias: &LatestAlias,
+  ) -> Result<()> {
+    let dir = self.crate_dir(lib_name)?;
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(LATEST_FILE_NAME), serde_json::to_vec(alias)?)?;
+    Ok(())
+  }
+
+  /// The directory of a crate, rejecting names that would escape the cache.
+  fn crate_dir(&self, lib_name: &str) -> Result<PathBuf> {
+    if !is_cache_key(lib_name) {
+      return Err(Error::Config(format!("invalid crate name `{lib_name}`")));
+    }
+    Ok(self.dir.join(lib_name))
+  }
+
+  /// The path of a cached document.
+  fn document_path(&self, lib_name: &str, version: &str) -> Result<PathBuf> {
+    if !is_cache_key(version) || version == "latest" {
+      return Err(Error::Config(format!("invalid crate version `{version}`")));
+    }
+    Ok(
+      self
+        .crate_dir(lib_name)?
+        .join(format!("{version}{DOCUMENT_EXTENSION}")),
+    )
+  }
+}
+
+/// Order two versions by semver precedence, falling back to comparing the
+/// strings when either is not a valid version.
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+  match (semver::Version::parse(a), semver::Version::parse(b)) {
+    (Ok(a), Ok(b)) => a.cmp(&b),
+    _ => a.cmp(b),
+  }
+}
+
+/// Whether a crate name or version can be used as a file name.
+fn is_cache_key(key: &str) -> bool {
+  !key.is_empty()
+    && !key.starts_with('.')
+    && key
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_store_load_and_prune() {
+    let dir = std::env::temp_dir()
+      .join(format!("crates_llms_txt_cache_{}", std::process::id()));
+    let cache = DocsCache::new(&dir);
+    assert!(cache.entries().unwrap().is_empty());
+
+    let json = br#"{"format_version":55}"#;
+    let payload = zstd::encode_all(&json[..], 0).unwrap();
+    cache.store("demo", "0.3.1", &payload).unwrap();
+    cache.store("demo", "0.3.0", &payload).unwrap();
+    cache.store("demo", "0.10.0", &payload).unwrap();
+    cache.store("other", "1.0.0", &payload).unwrap();
+    let alias = LatestAlias {
+      version: "0.3.1".to_string(),
+      etag: Some("\"abc\"".to_string()),
+      last_modified: None,
+    };
+    cache.set_latest("demo", &alias).unwrap();
+
+    assert_eq!(cache.load("demo", "0.3.1").unwrap().unwrap(), json);
+    assert_eq!(cache.load("demo", "0.2.0").unwrap(), None);
+    assert_eq!(cache.latest("demo").unwrap(), Some(alias));
+    assert_eq!(cache.latest("other").unwrap(), None);
+    let listed = cache
+      .entries()
+      .unwrap()
+      .into_iter()
+      .map(|entry| {
+        format!("{} {} {}", entry.lib_name, entry.version, entry.size)
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      listed,
+      [
+        format!("demo 0.3.0 {}", payload.len()),
+        format!("demo 0.3.1 {}", payload.len()),
+        format!("demo 0.10.0 {}", payload.len()),
+        format!("other 1.0.0 {}", payload.len()),
+      ]
+    );
+
+    // Removing the document `latest` resolves to removes the alias too
+    let removed = cache.prune(|entry| entry.lib_name == "demo").unwrap();
+    assert_eq!(removed.len(), 3);
+    assert_eq!(cache.latest("demo").unwrap(), None);
+    assert_eq!(cache.entries().unwrap().len(), 1);
+
+    assert!(cache.load("../demo", "0.3.1").is_err());
+    assert!(cache.load("demo", "latest").is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_compare_versions() {
+    use std::cmp::Ordering;
+
+    assert_eq!(compare_versions("0.9.0", "0.10.0"), Ordering::Less);
+    assert_eq!(compare_versions("1.0.0-beta.2", "1.0.0"), Ordering::Less);
+    assert_eq!(compare_versions("1.0.0", "1.0.0"), Ordering::Equal);
+    // Not semver: compared as strings
+    assert_eq!(compare_versions("1.0", "0.9.0"), Ordering::Greater);
+  }
+}
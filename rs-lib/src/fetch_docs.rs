@@ -1,37 +1,158 @@
 //! # Online Documentation Fetcher
 //!
 //! This module provides functionality to fetch and process rustdoc JSON data from online sources,
-//! primarily docs.rs. It handles automatic decompression of zstd-compressed data and provides
-//! fallback mechanisms for different rustdoc format versions.
+//! primarily docs.rs. It handles automatic decompression of zstd-compressed data and picks
+//! the deserializer matching the rustdoc format version of the downloaded data.
 //!
 //! ## Key Features
 //!
 //! - **Automatic Decompression**: Handles zstd compression used by docs.rs
-//! - **Version Compatibility**: Falls back between different rustdoc JSON formats
+//! - **Version Compatibility**: Dispatches on the `format_version` of the JSON, downloading it once
 //! - **Error Handling**: Comprehensive error handling for network and parsing issues
 //! - **Flexible URLs**: Support for both docs.rs and custom documentation servers
+//! - **Caching**: Optionally keeps downloaded documents in a `DocsCache`
+//! - **Configurable Client**: Timeouts, proxy, root certificates and per-host auth headers,
+//!   shared by every request made through an `OnlineDocs`
 
-use reqwest::header;
+use std::time::Duration;
+
+use reqwest::header::{self, HeaderName, HeaderValue};
+use reqwest::{Certificate, Proxy, StatusCode};
 use serde::{Deserialize, Serialize};
 
+use crate::cache::{DocsCache, LatestAlias};
 use crate::error::{Error, Result};
-use crate::temp_trait::{CommonCrates, Crate};
+use crate::temp_trait::{self, CommonCrates};
+use crate::versions::{self, ResolvedCrate};
+
+/// Magic number starting every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// A downloaded rustdoc JSON document.
+struct Document {
+  /// The rustdoc JSON, decompressed
+  json: Vec<u8>,
+  /// The body, as served
+  body: Vec<u8>,
+  /// The URL the document was served from, after redirects
+  url: String,
+  etag: Option<String>,
+  last_modified: Option<String>,
+}
+
+/// The outcome of revalidating a cached `latest` alias.
+enum Download {
+  /// The server answered `304 Not Modified`; the cached alias and document
+  /// are still current
+  NotModified { alias: LatestAlias, json: Vec<u8> },
+  /// The server sent a new document
+  Modified(Document),
+}
+
+impl Document {
+  /// The zstd payload stored in the cache: the body when it is already zstd,
+  /// the JSON compressed otherwise.
+  fn payload(&self) -> Result<Vec<u8>> {
+    if self.body.starts_with(&ZSTD_MAGIC) {
+      Ok(self.body.clone())
+    } else {
+      Ok(zstd::encode_all(self.json.as_slice(), 0)?)
+    }
+  }
+}
 
 /// Base URL for docs.rs crate documentation API endpoints
 const DOCS_BASE_URL: &str = "https://docs.rs/crate";
 
-/// Utility struct for fetching online documentation from docs.rs and other sources.
+/// User-Agent sent by default, identifying the library to docs.rs
+const DEFAULT_USER_AGENT: &str =
+  concat!("crates_llms_txt/", env!("CARGO_PKG_VERSION"));
+
+/// Time allowed to connect to a server by default
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client for fetching online documentation from docs.rs and other sources.
 ///
-/// This struct provides static methods for downloading, decompressing, and parsing
-/// rustdoc JSON data from various online sources.
-pub struct OnlineDocs;
+/// An `OnlineDocs` holds an HTTP client that is shared by every request made
+/// through it, so connections are reused across downloads. It is cheap to
+/// clone. `OnlineDocs::default()` uses the default settings; use
+/// `OnlineDocs::builder()` to set timeouts, a proxy, extra root certificates
+/// or the credentials of a private documentation server.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use crates_llms_txt::fetch_docs::OnlineDocs;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let online = OnlineDocs::builder()
+///     .timeout(Duration::from_secs(120))
+///     .proxy("http://proxy.example.com:8080")
+///     .bearer_auth("docs.example.com", "secret-token")
+///     .build()?;
+///
+///   let docs = online
+///     .fetch_docs_by_url("https://docs.example.com/crate/my-crate/1.0.0/json")
+///     .await?;
+///
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OnlineDocs {
+  client: reqwest::Client,
+  auth_headers: Vec<AuthHeader>,
+}
+
+/// A header only sent to one host.
+#[derive(Debug, Clone)]
+struct AuthHeader {
+  host: String,
+  name: HeaderName,
+  value: HeaderValue,
+}
+
+impl Default for OnlineDocs {
+  /// Create a client with the default settings.
+  ///
+  /// # Panics
+  ///
+  /// Like `reqwest::Client::new`, if the TLS backend cannot be initialized.
+  fn default() -> Self {
+    OnlineDocs::builder()
+      .build()
+      .expect("the default HTTP client should build")
+  }
+}
 
 impl OnlineDocs {
+  /// Creates a builder starting from the default settings.
+  pub fn builder() -> OnlineDocsBuilder {
+    OnlineDocsBuilder::default()
+  }
+
+  /// Start a GET request, with the auth headers configured for its host.
+  pub(crate) fn get(&self, url: &str) -> reqwest::RequestBuilder {
+    let mut request = self.client.get(url);
+    let host = reqwest::Url::parse(url)
+      .ok()
+      .and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+    for header in &self.auth_headers {
+      if host.as_deref() == Some(header.host.as_str()) {
+        request = request.header(header.name.clone(), header.value.clone());
+      }
+    }
+    request
+  }
+
   /// Fetch and parse JSON data from a URL, handling zstd compression automatically.
   ///
   /// This method automatically detects and decompresses zstd-compressed data from docs.rs,
-  /// then attempts to parse it into the specified type. It includes fallback error handling
-  /// for version compatibility issues between different rustdoc format versions.
+  /// then attempts to parse it into the specified type. Use `fetch_docs_by_url` to pick
+  /// the type from the rustdoc format version of the data.
   ///
   /// # Arguments
   ///
@@ -45,8 +166,9 @@ impl OnlineDocs {
   ///
   /// * `Error::Network` - If the HTTP request fails
   /// * `Error::Io` - If zstd decompression fails
-  /// * `Error::Json` - If JSON parsing fails due to malformed data
-  /// * `Error::Config` - If JSON structure is incompatible with the expected rustdoc-types version
+  /// * `Error::Json` - If JSON parsing fails, including documents whose
+  ///   structure does not match `T`; use [`OnlineDocs::fetch_docs`] to read
+  ///   any supported `format_version`
   ///
   /// # Examples
   ///
@@ -55,20 +177,32 @@ impl OnlineDocs {
   ///
   /// #[tokio::main]
   /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-  ///   let json = OnlineDocs::fetch_json::<rustdoc_types::Crate>(
-  ///     "https://docs.rs/crate/clap/latest/json"
-  ///   ).await?;
+  ///   let online = OnlineDocs::default();
+  ///   let json = online
+  ///     .fetch_json::<rustdoc_types::Crate>("https://docs.rs/crate/clap/latest/json")
+  ///     .await?;
   ///
   ///   Ok(())
   /// }
   /// ```
   ///
-  pub async fn fetch_json<T>(url: &str) -> Result<T>
+  pub async fn fetch_json<T>(&self, url: &str) -> Result<T>
   where
     T: CommonCrates + Serialize + for<'de> Deserialize<'de>,
   {
-    let client = reqwest::Client::builder().build()?;
-    let response = client.get(url).send().await?;
+    let json = self.fetch_bytes(url).await?;
+    serde_json::from_slice::<T>(&json).map_err(Error::Json)
+  }
+
+  /// Download a rustdoc JSON document, decompressed.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Network` - If the HTTP request fails or is answered with an
+  ///   error status
+  /// * `Error::Io` - If zstd decompression fails
+  async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
+    let response = self.get(url).send().await?.error_for_status()?;
     let headers = response.headers().clone();
 
     // Get the response body as raw bytes
@@ -83,14 +217,7 @@ impl OnlineDocs {
       .and_then(|value| value.to_str().ok());
 
     // Determine if we need to decompress
-    let decompressed_bytes = Self::decompress_if_needed(
-      &body_bytes,
-      content_encoding,
-      content_type,
-      url,
-    )?;
-
-    serde_json::from_slice::<T>(&decompressed_bytes).map_err(Error::Json)
+    Self::decompress_if_needed(&body_bytes, content_encoding, content_type, url)
   }
 
   /// Decompress zstd-compressed data from docs.rs endpoints.
@@ -150,16 +277,20 @@ impl OnlineDocs {
   /// # Arguments
   ///
   /// * `lib_name` - The name of the crate (e.g., "clap", "serde")
-  /// * `version` - The version of the crate. If None, "latest" will be used
+  /// * `version` - The version of the crate or a semver requirement (e.g.
+  ///   "~4.5"). If None, "latest" will be used
   ///
   /// # Returns
   ///
-  /// * `Result<rustdoc_types::Crate>` - The parsed rustdoc documentation data
+  /// * `Result<Box<dyn CommonCrates>>` - The parsed rustdoc documentation data,
+  ///   reporting the exact version it was resolved to
   ///
   /// # Errors
   ///
-  /// * `Error::Network` - If the HTTP request to docs.rs fails
-  /// * `Error::Config` - If the rustdoc format version is incompatible
+  /// * `Error::Network` - If the HTTP request to docs.rs or the crates.io
+  ///   index fails
+  /// * `Error::Config` - If the rustdoc format version is not supported, or
+  ///   no release matches the requested version
   /// * `Error::Json` - If the JSON data is malformed
   /// * `Error::Io` - If decompression fails
   ///
@@ -170,32 +301,257 @@ impl OnlineDocs {
   ///
   /// #[tokio::main]
   /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///   let online = OnlineDocs::default();
+  ///
   ///   // Fetch latest version
-  ///   let docs = OnlineDocs::fetch_docs("clap", None).await?;
+  ///   let docs = online.fetch_docs("clap", None).await?;
   ///   
   ///   // Fetch specific version
-  ///   let docs = OnlineDocs::fetch_docs("clap", Some("4.5.39".to_string())).await?;
+  ///   let docs = online.fetch_docs("clap", Some("4.5.39".to_string())).await?;
   ///
   ///   Ok(())
   /// }
   /// ```
   ///
   pub async fn fetch_docs(
+    &self,
     lib_name: &str,
     version: Option<String>,
   ) -> Result<Box<dyn CommonCrates>> {
-    let version = version.unwrap_or("latest".to_string());
+    let requested = version.unwrap_or("latest".to_string());
+    let version = versions::resolve_version(self, lib_name, &requested).await?;
     let url = format!("{DOCS_BASE_URL}/{lib_name}/{version}/json");
 
-    match OnlineDocs::fetch_json::<rustdoc_types::Crate>(url.as_str()).await {
-      Ok(result) => Ok(Box::new(result)),
-      Err(_) => match OnlineDocs::fetch_json::<Crate>(url.as_str()).await {
-        Ok(result) => Ok(Box::new(result)),
-        Err(err) => Err(err),
-      },
+    let docs = self.fetch_docs_by_url(&url).await?;
+    Ok(Box::new(ResolvedCrate { docs, version }))
+  }
+
+  /// Fetch rustdoc documentation from docs.rs through an on-disk cache.
+  ///
+  /// Released versions are served from the cache once downloaded. `latest`
+  /// is revalidated with docs.rs using the `ETag` and `Last-Modified` of the
+  /// previous response, and only downloaded again when it changed. Semver
+  /// requirements are resolved against the crates.io index, or against the
+  /// cached versions in offline mode, where the cache is the only source.
+  ///
+  /// # Arguments
+  ///
+  /// * `lib_name` - The name of the crate (e.g., "clap", "serde")
+  /// * `version` - The version of the crate or a semver requirement (e.g.
+  ///   "~4.5"). If None, "latest" will be used
+  /// * `cache` - The cache to read from and store into
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Box<dyn CommonCrates>>` - The parsed rustdoc documentation data
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Network` - If the HTTP request to docs.rs fails
+  /// * `Error::Config` - If the rustdoc format version is not supported, no
+  ///   release matches the requested version, or the document is not cached
+  ///   in offline mode
+  /// * `Error::Json` - If the JSON data is malformed
+  /// * `Error::Io` - If the cache cannot be read or written
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use crates_llms_txt::cache::DocsCache;
+  /// use crates_llms_txt::fetch_docs::OnlineDocs;
+  ///
+  /// #[tokio::main]
+  /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///   let cache = DocsCache::new("./.docs-cache");
+  ///   let online = OnlineDocs::default();
+  ///   let docs = online.fetch_docs_cached("clap", None, &cache).await?;
+  ///
+  ///   Ok(())
+  /// }
+  /// ```
+  ///
+  pub async fn fetch_docs_cached(
+    &self,
+    lib_name: &str,
+    version: Option<String>,
+    cache: &DocsCache,
+  ) -> Result<Box<dyn CommonCrates>> {
+    let requested = version.unwrap_or("latest".to_string());
+    let not_cached = || {
+      Error::Config(format!(
+        "{lib_name} {requested} is not in the docs cache (offline mode)"
+      ))
+    };
+
+    if requested.trim() != "latest" {
+      let version = match versions::exact_version(&requested) {
+        Some(version) => version,
+        None if cache.is_offline() => {
+          let cached = cache
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.lib_name == lib_name)
+            .map(|entry| entry.version);
+          versions::select_version(cached, &requested)?
+            .ok_or_else(not_cached)?
+        }
+        None => versions::resolve_version(self, lib_name, &requested).await?,
+      };
+      if let Some(json) = cache.load(lib_name, &version)? {
+        let docs = temp_trait::parse_crate(&json)?;
+        return Ok(Box::new(ResolvedCrate { docs, version }));
+      }
+      if cache.is_offline() {
+        return Err(not_cached());
+      }
+      let url = format!("{DOCS_BASE_URL}/{lib_name}/{version}/json");
+      let document = self.download(&url).await?;
+      cache.store(lib_name, &version, &document.payload()?)?;
+      let docs = temp_trait::parse_crate(&document.json)?;
+      return Ok(Box::new(ResolvedCrate { docs, version }));
+    }
+
+    // Revalidate the cached `latest` only while its document is still cached
+    let mut cached = None;
+    if let Some(alias) = cache.latest(lib_name)? {
+      if let Some(json) = cache.load(lib_name, &alias.version)? {
+        cached = Some((alias, json));
+      }
+    }
+    if cache.is_offline() {
+      let (alias, json) = cached.ok_or_else(not_cached)?;
+      let docs = temp_trait::parse_crate(&json)?;
+      return Ok(Box::new(ResolvedCrate {
+        docs,
+        version: alias.version,
+      }));
+    }
+    let url = format!("{DOCS_BASE_URL}/{lib_name}/latest/json");
+    match self.revalidate(&url, cached).await? {
+      Download::NotModified { alias, json } => {
+        let docs = temp_trait::parse_crate(&json)?;
+        Ok(Box::new(ResolvedCrate {
+          docs,
+          version: alias.version,
+        }))
+      }
+      Download::Modified(document) => {
+        let version = self.document_version(&document, lib_name).await?;
+        cache.store(lib_name, &version, &document.payload()?)?;
+        cache.set_latest(
+          lib_name,
+          &LatestAlias {
+            version: version.clone(),
+            etag: document.etag,
+            last_modified: document.last_modified,
+          },
+        )?;
+        let docs = temp_trait::parse_crate(&document.json)?;
+        Ok(Box::new(ResolvedCrate { docs, version }))
+      }
     }
   }
 
+  /// The exact version of a downloaded document.
+  ///
+  /// Older documents do not record their version; docs.rs redirects `latest`
+  /// to a versioned URL, and the crates.io index is the last resort.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Json` - If the document is not a JSON object
+  /// * `Error::Network`, `Error::Config` - If the version has to be looked up
+  ///   in the index and the lookup fails
+  async fn document_version(
+    &self,
+    document: &Document,
+    lib_name: &str,
+  ) -> Result<String> {
+    match temp_trait::crate_version(&document.json)?
+      .or_else(|| version_from_url(&document.url, lib_name))
+    {
+      Some(version) => Ok(version),
+      None => versions::resolve_version(self, lib_name, "latest").await,
+    }
+  }
+
+  /// Revalidate a cached `latest` alias and its document, downloading the
+  /// document again unless the server answers `304 Not Modified`.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Network` - If the HTTP request fails or is answered with an
+  ///   error status
+  /// * `Error::Io` - If zstd compression or decompression fails
+  async fn revalidate(
+    &self,
+    url: &str,
+    cached: Option<(LatestAlias, Vec<u8>)>,
+  ) -> Result<Download> {
+    let mut request = self.get(url);
+    if let Some((alias, _)) = &cached {
+      if let Some(etag) = &alias.etag {
+        request = request.header(header::IF_NONE_MATCH, etag);
+      }
+      if let Some(last_modified) = &alias.last_modified {
+        request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+      }
+    }
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+      if let Some((alias, json)) = cached {
+        return Ok(Download::NotModified { alias, json });
+      }
+    }
+    Ok(Download::Modified(
+      Self::read_document(url, response).await?,
+    ))
+  }
+
+  /// Download a rustdoc JSON document.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Network` - If the HTTP request fails or is answered with an
+  ///   error status
+  /// * `Error::Io` - If zstd compression or decompression fails
+  async fn download(&self, url: &str) -> Result<Document> {
+    let response = self.get(url).send().await?;
+    Self::read_document(url, response).await
+  }
+
+  /// Read the document of a response, with its validators.
+  async fn read_document(
+    url: &str,
+    response: reqwest::Response,
+  ) -> Result<Document> {
+    let response = response.error_for_status()?;
+
+    let response_url = response.url().to_string();
+    let headers = response.headers().clone();
+    let header_value = |name| {
+      headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+    };
+    let body_bytes = response.bytes().await?;
+    let json = Self::decompress_if_needed(
+      &body_bytes,
+      header_value(header::CONTENT_ENCODING).as_deref(),
+      header_value(header::CONTENT_TYPE).as_deref(),
+      url,
+    )?;
+
+    Ok(Document {
+      json,
+      body: body_bytes.to_vec(),
+      url: response_url,
+      etag: header_value(header::ETAG),
+      last_modified: header_value(header::LAST_MODIFIED),
+    })
+  }
+
   /// Fetch rustdoc documentation from a custom URL.
   ///
   /// This method allows fetching documentation from any URL that serves rustdoc JSON data,
@@ -207,12 +563,16 @@ impl OnlineDocs {
   ///
   /// # Returns
   ///
-  /// * `Result<Crate>` - The parsed rustdoc documentation data using the internal Crate type
+  /// * `Result<Box<dyn CommonCrates>>` - The parsed rustdoc documentation data, read with
+  ///   the deserializer for its format version. Its `crate_version` is
+  ///   exact: when the document does not record it, it is taken from the URL
+  ///   the document was served from, or resolved against the crates.io index
   ///
   /// # Errors
   ///
   /// * `Error::Network` - If the HTTP request fails
-  /// * `Error::Config` - If the rustdoc format version is incompatible
+  /// * `Error::Config` - If the rustdoc format version is not supported, or
+  ///   the version of the document cannot be resolved
   /// * `Error::Json` - If the JSON data is malformed
   /// * `Error::Io` - If decompression fails
   ///
@@ -223,45 +583,333 @@ impl OnlineDocs {
   ///
   /// #[tokio::main]
   /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///   let online = OnlineDocs::default();
+  ///
   ///   // Fetch from docs.rs
-  ///   let docs = OnlineDocs::fetch_docs_by_url(
-  ///     "https://docs.rs/crate/clap/latest/json"
-  ///   ).await?;
+  ///   let docs = online
+  ///     .fetch_docs_by_url("https://docs.rs/crate/clap/latest/json")
+  ///     .await?;
   ///   
   ///   // Fetch from custom documentation server
-  ///   let docs = OnlineDocs::fetch_docs_by_url(
-  ///     "https://my-docs-server.com/crate/my-crate/1.0.0/json"
-  ///   ).await?;
+  ///   let docs = online
+  ///     .fetch_docs_by_url("https://my-docs-server.com/crate/my-crate/1.0.0/json")
+  ///     .await?;
   ///
   ///   Ok(())
   /// }
   /// ```
   ///
-  pub async fn fetch_docs_by_url(url: &str) -> Result<Box<dyn CommonCrates>> {
-    match OnlineDocs::fetch_json::<rustdoc_types::Crate>(url).await {
-      Ok(result) => Ok(Box::new(result)),
-      Err(_) => match OnlineDocs::fetch_json::<Crate>(url).await {
-        Ok(result) => Ok(Box::new(result)),
-        Err(err) => Err(err),
-      },
+  pub async fn fetch_docs_by_url(
+    &self,
+    url: &str,
+  ) -> Result<Box<dyn CommonCrates>> {
+    // Download once and let the format version pick the deserializer
+    let document = self.download(url).await?;
+    let docs = temp_trait::parse_crate(&document.json)?;
+    let Some(lib_name) = docs.root_name() else {
+      return Err(Error::Config(
+        "Failed to extract crate name from root item".into(),
+      ));
+    };
+    let version = self.document_version(&document, &lib_name).await?;
+    Ok(Box::new(ResolvedCrate { docs, version }))
+  }
+}
+
+/// Builder for [`OnlineDocs`].
+#[derive(Debug, Clone)]
+pub struct OnlineDocsBuilder {
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  user_agent: String,
+  proxy: Option<String>,
+  root_certificates: Vec<Vec<u8>>,
+  auth_headers: Vec<(String, String, String)>,
+}
+
+impl Default for OnlineDocsBuilder {
+  fn default() -> Self {
+    Self {
+      timeout: None,
+      connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+      user_agent: DEFAULT_USER_AGENT.to_string(),
+      proxy: None,
+      root_certificates: Vec::new(),
+      auth_headers: Vec::new(),
     }
   }
 }
 
+impl OnlineDocsBuilder {
+  /// Limit the total time of a request, from connecting until the body has
+  /// been read. Requests are not limited by default, as the rustdoc JSON of
+  /// large crates can take a while to download.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Limit the time to connect to a server; 30 seconds by default.
+  pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+    self.connect_timeout = Some(timeout);
+    self
+  }
+
+  /// Set the `User-Agent` sent with every request; `crates_llms_txt/<version>`
+  /// by default.
+  pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+    self.user_agent = user_agent.into();
+    self
+  }
+
+  /// Send every request through a proxy, e.g. `http://proxy:8080` or
+  /// `socks5://proxy:1080`, instead of the one of the `HTTP_PROXY` and
+  /// `HTTPS_PROXY` environment variables.
+  pub fn proxy(mut self, url: impl Into<String>) -> Self {
+    self.proxy = Some(url.into());
+    self
+  }
+
+  /// Trust a PEM-encoded root certificate in addition to the system ones;
+  /// may be called several times.
+  pub fn root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+    self.root_certificates.push(pem.into());
+    self
+  }
+
+  /// Send a header with every request to `host`, e.g. the credentials of a
+  /// private documentation server; may be called several times. Requests to
+  /// other hosts, such as docs.rs and the crates.io index, never carry it.
+  pub fn auth_header(
+    mut self,
+    host: impl Into<String>,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) -> Self {
+    self
+      .auth_headers
+      .push((host.into(), name.into(), value.into()));
+    self
+  }
+
+  /// Send `Authorization: Bearer <token>` with every request to `host`.
+  pub fn bearer_auth(
+    self,
+    host: impl Into<String>,
+    token: impl AsRef<str>,
+  ) -> Self {
+    let value = format!("Bearer {}", token.as_ref());
+    self.auth_header(host, header::AUTHORIZATION.as_str(), value)
+  }
+
+  /// Build the client.
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Config` - If the proxy URL, a root certificate or an auth
+  ///   header is invalid
+  /// * `Error::Network` - If the TLS backend cannot be initialized
+  pub fn build(self) -> Result<OnlineDocs> {
+    let mut builder = reqwest::Client::builder().user_agent(self.user_agent);
+    if let Some(timeout) = self.timeout {
+      builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
+    if let Some(url) = self.proxy {
+      let proxy = Proxy::all(&url).map_err(|err| {
+        Error::Config(format!("invalid proxy `{url}`: {err}"))
+      })?;
+      builder = builder.proxy(proxy);
+    }
+    for pem in self.root_certificates {
+      let certificate = Certificate::from_pem(&pem).map_err(|err| {
+        Error::Config(format!("invalid root certificate: {err}"))
+      })?;
+      builder = builder.add_root_certificate(certificate);
+    }
+
+    let auth_headers = self
+      .auth_headers
+      .into_iter()
+      .map(|(host, name, value)| {
+        let invalid =
+          || Error::Config(format!("invalid auth header `{name}` for {host}"));
+        let header_name =
+          HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+        let mut header_value =
+          HeaderValue::from_str(&value).map_err(|_| invalid())?;
+        header_value.set_sensitive(true);
+        Ok(AuthHeader {
+          host: host.to_ascii_lowercase(),
+          name: header_name,
+          value: header_value,
+        })
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(OnlineDocs {
+      client: builder.build()?,
+      auth_headers,
+    })
+  }
+}
+
+/// The version in a docs.rs URL such as
+/// `https://static.docs.rs/clap/4.5.39/json.zst`, which `latest` redirects to.
+///
+/// `-` and `_` are equivalent in the crate name, which rustdoc reports with
+/// `_` whatever the package name.
+fn version_from_url(url: &str, lib_name: &str) -> Option<String> {
+  let path = reqwest::Url::parse(url).ok()?;
+  let mut segments = path.path_segments()?;
+  let lib_name = lib_name.replace('-', "_");
+  segments.find(|segment| segment.replace('-', "_") == lib_name)?;
+  segments.next().and_then(versions::exact_version)
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::{temp_trait::Crate, OnlineDocs, DOCS_BASE_URL};
+  use super::{header, version_from_url, Document, OnlineDocs, DOCS_BASE_URL};
+  use crate::cache::{DocsCache, LatestAlias};
+  use crate::error::Error;
+  use crate::temp_trait::Crate;
+
+  #[tokio::test]
+  async fn test_fetch_docs_cached_offline() {
+    let dir = std::env::temp_dir().join(format!(
+      "crates_llms_txt_fetch_cache_{}",
+      std::process::id()
+    ));
+    let cache = DocsCache::new(&dir).offline(true);
+    let online = OnlineDocs::default();
+    let json = include_bytes!("../tests/fixtures/demo.json");
+    cache
+      .store("demo", "0.3.1", &zstd::encode_all(&json[..], 0).unwrap())
+      .unwrap();
+    cache
+      .set_latest(
+        "demo",
+        &LatestAlias {
+          version: "0.3.1".to_string(),
+          etag: None,
+          last_modified: None,
+        },
+      )
+      .unwrap();
+
+    for version in [None, Some("0.3.1".to_string()), Some("^0.3".to_string())] {
+      let docs = online
+        .fetch_docs_cached("demo", version, &cache)
+        .await
+        .unwrap();
+      assert_eq!(docs.crate_version().as_deref(), Some("0.3.1"));
+    }
+    let missing = online
+      .fetch_docs_cached("demo", Some("0.2.0".to_string()), &cache)
+      .await;
+    assert!(matches!(missing, Err(Error::Config(_))));
+    let unmatched = online
+      .fetch_docs_cached("demo", Some("~0.2".to_string()), &cache)
+      .await;
+    assert!(matches!(unmatched, Err(Error::Config(_))));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_auth_headers_are_scoped_to_their_host() {
+    let online = OnlineDocs::builder()
+      .user_agent("docs-bot/1.0")
+      .bearer_auth("Docs.Example.com", "secret")
+      .auth_header("docs.example.com", "X-Team", "rust")
+      .build()
+      .unwrap();
+    let headers = |url| online.get(url).build().unwrap().headers().clone();
+
+    let private = headers("https://docs.example.com/crate/demo/1.0.0/json");
+    assert_eq!(private[header::AUTHORIZATION], "Bearer secret");
+    assert!(private[header::AUTHORIZATION].is_sensitive());
+    assert_eq!(private["x-team"], "rust");
+    let public = headers("https://docs.rs/crate/demo/latest/json");
+    assert!(public.get(header::AUTHORIZATION).is_none());
+    assert!(public.get("x-team").is_none());
+  }
+
+  #[test]
+  fn test_builder_rejects_invalid_settings() {
+    let invalid = [
+      OnlineDocs::builder().proxy("not a proxy"),
+      OnlineDocs::builder().root_certificate("not a certificate"),
+      OnlineDocs::builder().auth_header("docs.example.com", "X Team", "rust"),
+      OnlineDocs::builder().bearer_auth("docs.example.com", "line\nbreak"),
+    ];
+    for builder in invalid {
+      assert!(matches!(builder.build(), Err(Error::Config(_))));
+    }
+  }
+
+  #[tokio::test]
+  async fn test_document_version() {
+    let online = OnlineDocs::default();
+    let document = |json: &str, url: &str| Document {
+      json: json.as_bytes().to_vec(),
+      body: json.as_bytes().to_vec(),
+      url: url.to_string(),
+      etag: None,
+      last_modified: None,
+    };
+    let versioned = "https://static.docs.rs/demo/0.3.0/json.zst";
+
+    // The recorded version wins over the URL
+    let recorded = document(r#"{"crate_version":"0.3.1"}"#, versioned);
+    assert_eq!(
+      online.document_version(&recorded, "demo").await.unwrap(),
+      "0.3.1"
+    );
+    // Older documents fall back to the URL they were served from
+    let unrecorded = document(r#"{"crate_version":null}"#, versioned);
+    assert_eq!(
+      online.document_version(&unrecorded, "demo").await.unwrap(),
+      "0.3.0"
+    );
+  }
+
+  #[test]
+  fn test_version_from_url() {
+    assert_eq!(
+      version_from_url("https://static.docs.rs/clap/4.5.39/json.zst", "clap")
+        .as_deref(),
+      Some("4.5.39")
+    );
+    assert_eq!(
+      version_from_url("https://docs.rs/crate/clap/latest/json", "clap"),
+      None
+    );
+    assert_eq!(
+      version_from_url(
+        "https://static.docs.rs/rustdoc-types/0.55.0/json.zst",
+        "rustdoc_types"
+      )
+      .as_deref(),
+      Some("0.55.0")
+    );
+    assert_eq!(version_from_url("not a url", "clap"), None);
+  }
 
   #[tokio::test]
   async fn test_fetch_docs() {
     let version = "latest".to_string();
-    let docs = OnlineDocs::fetch_docs("clap", Some(version.clone()))
+    let docs = OnlineDocs::default()
+      .fetch_docs("clap", Some(version.clone()))
       .await
       .unwrap();
 
-    // Just check that we got some docs, don't assert on specific version
-    // since "latest" will change over time
-    assert!(!docs.crate_version().is_empty());
+    // Don't assert on a specific version since "latest" will change over
+    // time, only that it was resolved to one
+    let version = docs.crate_version().unwrap();
+    assert!(crate::versions::exact_version(&version).is_some());
     println!(
       "Successfully fetched clap docs, version: {:?}",
       docs.crate_version()
@@ -272,13 +920,15 @@ mod tests {
   async fn test_fetch_docs_opendal() {
     let version = "latest".to_string();
 
-    let docs = OnlineDocs::fetch_docs("opendal", Some(version.clone()))
+    let docs = OnlineDocs::default()
+      .fetch_docs("opendal", Some(version.clone()))
       .await
       .unwrap();
 
     println!("Successfully fetched docs for opendal");
     println!("Crate version: {:?}", docs.crate_version());
-    assert!(!docs.crate_version().is_empty());
+    let version = docs.crate_version().unwrap();
+    assert!(crate::versions::exact_version(&version).is_some());
   }
 
   #[tokio::test]
@@ -305,7 +955,8 @@ mod tests {
   #[tokio::test]
   async fn test_fetch_docs_json_validation_x() {
     let version = "latest".to_string();
-    let json_valid = OnlineDocs::fetch_docs("serde", Some(version.clone()))
+    let json_valid = OnlineDocs::default()
+      .fetch_docs("serde", Some(version.clone()))
       .await
       .is_ok();
 
@@ -0,0 +1,120 @@
+//! # Field and Variant Listing
+//!
+//! Struct fields and enum variants are separate items of the rustdoc JSON
+//! index, referenced from their parent by id. This module collects them so
+//! that they can be documented as part of their parent, as on its rustdoc
+//! page.
+
+use std::collections::HashMap;
+
+use rustdoc_types::{Id, ItemEnum, StructKind, VariantKind};
+
+use crate::temp_trait::Item;
+
+/// Collect the fields of a struct, union or enum variant.
+///
+/// # Arguments
+///
+/// * `item` - The struct, union or variant
+/// * `index` - The crate index, from `CommonCrates::index`
+///
+/// # Returns
+///
+/// * `Vec<&Item>` - The fields in declaration order, without stripped tuple
+///   fields; empty for other kinds of items
+pub fn fields<'a>(item: &Item, index: &'a HashMap<Id, Item>) -> Vec<&'a Item> {
+  let ids = match &item.inner {
+    ItemEnum::Struct(struct_) => match &struct_.kind {
+      StructKind::Plain { fields, .. } => fields.clone(),
+      StructKind::Tuple(fields) => fields.iter().flatten().copied().collect(),
+      StructKind::Unit => Vec::new(),
+    },
+    ItemEnum::Union(union) => union.fields.clone(),
+    ItemEnum::Variant(variant) => match &variant.kind {
+      VariantKind::Struct { fields, .. } => fields.clone(),
+      VariantKind::Tuple(fields) => fields.iter().flatten().copied().collect(),
+      VariantKind::Plain => Vec::new(),
+    },
+    _ => Vec::new(),
+  };
+  ids.iter().filter_map(|id| index.get(id)).collect()
+}
+
+/// Collect the variants of an enum, in declaration order.
+pub fn variants<'a>(
+  item: &Item,
+  index: &'a HashMap<Id, Item>,
+) -> Vec<&'a Item> {
+  match &item.inner {
+    ItemEnum::Enum(enum_) => enum_
+      .variants
+      .iter()
+      .filter_map(|id| index.get(id))
+      .collect(),
+    _ => Vec::new(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::{fixture_crate, process, session, titles};
+
+  #[test]
+  fn test_fields_and_variants() {
+    let index = fixture_crate().index();
+    let find = |name: &str| {
+      index
+        .values()
+        .find(|item| {
+          item.name.as_deref() == Some(name)
+            && !matches!(item.inner, ItemEnum::Use(_))
+        })
+        .unwrap()
+    };
+    let names = |items: Vec<&Item>| {
+      items
+        .into_iter()
+        .filter_map(|item| item.name.clone())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      names(fields(find("Parser"), &index)),
+      ["input", "keep_empty"]
+    );
+    assert_eq!(
+      names(variants(find("Token"), &index)),
+      ["Ident", "Number", "Eof"]
+    );
+    assert_eq!(names(fields(find("Number"), &index)), ["value", "signed"]);
+    assert_eq!(names(fields(find("Ident"), &index)), ["0"]);
+    assert!(fields(find("Eof"), &index).is_empty());
+    assert!(variants(find("Parser"), &index).is_empty());
+  }
+
+  #[test]
+  fn test_process_docs_fields_and_variants() {
+    let docs = process(&ProcessOptions::default());
+
+    assert!(!titles(&docs).iter().any(|title| {
+      ["input", "offset", "value"].contains(title)
+        || title.starts_with("demo::parser::Token::")
+    }));
+
+    let (_, parser) = session(&docs, "demo::parser::Parser");
+    let fields = parser.content.find("\n# Fields\n\n`input: &'a str`\n\nThe input being parsed.\n\n`keep_empty: bool`").unwrap();
+    assert!(fields < parser.content.find("\n# Implementations\n").unwrap());
+
+    let (_, token) = session(&docs, "demo::parser::Token");
+    assert!(token.content.contains(
+      "\n# Variants\n\n`Ident(String)`\n\nAn identifier.\n\n\
+       `Number { value: i64, signed: bool }`\n\nA number literal.\n\n\
+       - `value: i64`\n\n  The parsed value.\n\n\
+       - `signed: bool`\n\n  Whether the literal had a sign.\n\n\
+       `Eof`\n\nEnd of input."
+    ));
+  }
+}
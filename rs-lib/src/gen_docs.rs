@@ -11,7 +11,8 @@
 //! - **Auto-detection**: Automatically detect the appropriate toolchain to use
 //! - **Error Handling**: Comprehensive error reporting for build failures
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use crate::error::{Error, Result};
 use crate::temp_trait::Crate;
@@ -73,7 +74,7 @@ pub fn gen_docs_with_all_features(
     _ => rustdoc_json_stable::Builder::stable(),
   }
   .toolchain(toolchain)
-  .manifest_path(manifest_path)
+  .manifest_path(&manifest_path)
   .all_features(true) // Enable all available features for comprehensive docs
   .quiet(true) // Suppress cargo output for cleaner execution
   .build()?;
@@ -89,7 +90,8 @@ pub fn gen_docs_with_all_features(
 
   // Read and parse the generated JSON documentation
   let json_string = std::fs::read_to_string(&json_path)?;
-  let json_data: Crate = serde_json::from_str(&json_string)?;
+  let mut json_data: Crate = serde_json::from_str(&json_string)?;
+  fill_crate_version(&mut json_data, &manifest_path)?;
 
   Ok(GenDocs {
     lib_name,
@@ -124,7 +126,7 @@ pub fn gen_docs_with_all_features_auto_toolchain(
     true => rustdoc_json_stable::Builder::default().toolchain("nightly"),
     false => rustdoc_json_stable::Builder::stable().toolchain("stable"),
   }
-  .manifest_path(manifest_path)
+  .manifest_path(&manifest_path)
   .all_features(true) // Enable all features for comprehensive documentation
   .quiet(true) // Suppress cargo output
   .build()?;
@@ -140,7 +142,8 @@ pub fn gen_docs_with_all_features_auto_toolchain(
 
   // Load and parse the generated documentation JSON
   let json_string = std::fs::read_to_string(&json_path)?;
-  let json_data: Crate = serde_json::from_str(&json_string)?;
+  let mut json_data: Crate = serde_json::from_str(&json_string)?;
+  fill_crate_version(&mut json_data, &manifest_path)?;
 
   Ok(GenDocs {
     lib_name,
@@ -206,7 +209,7 @@ pub fn gen_docs_with_features(
     _ => rustdoc_json_stable::Builder::stable(),
   }
   .toolchain(toolchain)
-  .manifest_path(manifest_path)
+  .manifest_path(&manifest_path)
   .quiet(true); // Suppress cargo output for cleaner execution
 
   // Configure feature settings based on parameters
@@ -232,7 +235,8 @@ pub fn gen_docs_with_features(
 
   // Read and parse the generated documentation
   let json_string = std::fs::read_to_string(&json_path)?;
-  let json_data: Crate = serde_json::from_str(&json_string)?;
+  let mut json_data: Crate = serde_json::from_str(&json_string)?;
+  fill_crate_version(&mut json_data, &manifest_path)?;
 
   Ok(GenDocs {
     lib_name,
@@ -275,7 +279,7 @@ pub fn gen_docs_with_features_auto_toolchain(
     true => rustdoc_json_stable::Builder::default().toolchain("nightly"),
     false => rustdoc_json_stable::Builder::stable().toolchain("stable"),
   }
-  .manifest_path(manifest_path)
+  .manifest_path(&manifest_path)
   .quiet(true); // Suppress cargo output
 
   // Apply feature configuration
@@ -299,7 +303,8 @@ pub fn gen_docs_with_features_auto_toolchain(
 
   // Load and parse the documentation data
   let json_string = std::fs::read_to_string(&json_path)?;
-  let json_data: Crate = serde_json::from_str(&json_string)?;
+  let mut json_data: Crate = serde_json::from_str(&json_string)?;
+  fill_crate_version(&mut json_data, &manifest_path)?;
 
   Ok(GenDocs {
     lib_name,
@@ -307,6 +312,61 @@ pub fn gen_docs_with_features_auto_toolchain(
   })
 }
 
+/// Record the package version of the manifest in documentation generated
+/// without `crate_version`, so that it never has to be guessed.
+///
+/// # Errors
+///
+/// * `Error::Io` - If `cargo metadata` cannot be run
+/// * `Error::Json` - If its output cannot be parsed
+/// * `Error::Config` - If it fails or does not list the package
+fn fill_crate_version(docs: &mut Crate, manifest_path: &Path) -> Result<()> {
+  if docs.crate_version.is_none() {
+    docs.crate_version = Some(manifest_version(manifest_path)?);
+  }
+  Ok(())
+}
+
+/// The version of the package described by a manifest, as resolved by
+/// `cargo metadata`, which also applies `version.workspace = true`.
+fn manifest_version(manifest_path: &Path) -> Result<String> {
+  #[derive(serde::Deserialize)]
+  struct Metadata {
+    packages: Vec<Package>,
+  }
+  #[derive(serde::Deserialize)]
+  struct Package {
+    version: String,
+    manifest_path: PathBuf,
+  }
+
+  let output = Command::new("cargo")
+    .args(["metadata", "--no-deps", "--format-version", "1"])
+    .arg("--manifest-path")
+    .arg(manifest_path)
+    .output()?;
+  if !output.status.success() {
+    return Err(Error::Config(format!(
+      "cargo metadata failed for {}: {}",
+      manifest_path.display(),
+      String::from_utf8_lossy(&output.stderr).trim()
+    )));
+  }
+  let metadata = serde_json::from_slice::<Metadata>(&output.stdout)?;
+  let manifest_path = manifest_path.canonicalize()?;
+  metadata
+    .packages
+    .into_iter()
+    .find(|package| package.manifest_path == manifest_path)
+    .map(|package| package.version)
+    .ok_or_else(|| {
+      Error::Config(format!(
+        "no package is described by {}",
+        manifest_path.display()
+      ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -392,4 +452,19 @@ mod tests {
 
     assert_eq!(gen_docs_struct.lib_name, "crates_llms_txt");
   }
+
+  #[test]
+  fn test_manifest_version() {
+    let current_dir = env::current_dir().unwrap();
+    assert_eq!(
+      manifest_version(&current_dir.join("Cargo.toml")).unwrap(),
+      env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(
+      manifest_version(&current_dir.join("tests/fixtures/demo/Cargo.toml"))
+        .unwrap(),
+      "0.3.1"
+    );
+    assert!(manifest_version(Path::new("nonexistent/Cargo.toml")).is_err());
+  }
 }
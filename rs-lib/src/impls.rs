@@ -0,0 +1,181 @@
+//! # Implementation Listing
+//!
+//! Methods are not children of their type in rustdoc JSON: they live in
+//! `ItemEnum::Impl` blocks referenced from `Struct::impls`, `Enum::impls` and
+//! `Union::impls`. This module walks those blocks to collect the associated
+//! items of inherent impls, which are documented as part of their type, and
+//! the traits a type implements.
+
+use std::collections::{HashMap, HashSet};
+
+use rustdoc_types::{Id, Impl, ItemEnum};
+
+use crate::signature;
+use crate::temp_trait::Item;
+
+/// The impl blocks of a struct, enum or union.
+pub fn type_impls(inner: &ItemEnum) -> &[Id] {
+  match inner {
+    ItemEnum::Struct(struct_) => &struct_.impls,
+    ItemEnum::Enum(enum_) => &enum_.impls,
+    ItemEnum::Union(union) => &union.impls,
+    _ => &[],
+  }
+}
+
+/// Collect the associated items of the inherent impls of a type.
+///
+/// # Arguments
+///
+/// * `item` - The struct, enum or union
+/// * `index` - The crate index, from `CommonCrates::index`
+///
+/// # Returns
+///
+/// * `Vec<&Item>` - Methods, associated constants and types, in declaration
+///   order; empty for other kinds of items
+pub fn inherent_items<'a>(
+  item: &Item,
+  index: &'a HashMap<Id, Item>,
+) -> Vec<&'a Item> {
+  impls(item, index)
+    .filter(|impl_| impl_.trait_.is_none())
+    .flat_map(|impl_| impl_.items.iter())
+    .filter_map(|id| index.get(id))
+    .collect()
+}
+
+/// Collect the ids of every associated item of an inherent impl in the crate.
+///
+/// These items are documented as part of their type and have no session of
+/// their own.
+pub fn all_inherent_items(index: &HashMap<Id, Item>) -> HashSet<Id> {
+  index
+    .values()
+    .flat_map(|item| inherent_items(item, index))
+    .map(|member| member.id)
+    .collect()
+}
+
+/// List the traits a type implements, e.g. `Clone` or `From<&'a str>`.
+///
+/// Negative impls are prefixed with `!`. Auto trait impls (`Send`, `Sync`,
+/// ...) and blanket impls (`impl<T> From<T> for T`, ...) follow the other
+/// impls, as on rustdoc pages, and are only listed when `include_blanket` is
+/// set.
+///
+/// # Arguments
+///
+/// * `item` - The struct, enum or union
+/// * `index` - The crate index, from `CommonCrates::index`
+/// * `include_blanket` - Whether to list auto trait and blanket impls
+///
+/// # Returns
+///
+/// * `Vec<String>` - The implemented traits, in declaration order within
+///   each group and without duplicates
+pub fn implemented_traits(
+  item: &Item,
+  index: &HashMap<Id, Item>,
+  include_blanket: bool,
+) -> Vec<String> {
+  let mut trait_impls = impls(item, index)
+    .filter(|impl_| impl_.trait_.is_some())
+    .filter(|impl_| {
+      include_blanket || !(impl_.is_synthetic || impl_.blanket_impl.is_some())
+    })
+    .collect::<Vec<_>>();
+  trait_impls
+    .sort_by_key(|impl_| (impl_.blanket_impl.is_some(), impl_.is_synthetic));
+
+  let mut traits = Vec::new();
+  for impl_ in trait_impls {
+    let Some(trait_) = &impl_.trait_ else {
+      continue;
+    };
+    let negation = if impl_.is_negative { "!" } else { "" };
+    let name = format!("{negation}{}", signature::path_to_string(trait_));
+    if !traits.contains(&name) {
+      traits.push(name);
+    }
+  }
+  traits
+}
+
+/// The impl blocks of a type, resolved from the index.
+fn impls<'a>(
+  item: &'a Item,
+  index: &'a HashMap<Id, Item>,
+) -> impl Iterator<Item = &'a Impl> {
+  type_impls(&item.inner).iter().filter_map(|id| {
+    match index.get(id).map(|impl_| &impl_.inner) {
+      Some(ItemEnum::Impl(impl_)) => Some(impl_),
+      _ => None,
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::{fixture_crate, process, session, titles};
+
+  #[test]
+  fn test_parser_impls() {
+    let index = fixture_crate().index();
+    let parser = index
+      .values()
+      .find(|item| {
+        item.name.as_deref() == Some("Parser")
+          && matches!(item.inner, ItemEnum::Struct(_))
+      })
+      .unwrap();
+
+    let methods = inherent_items(parser, &index)
+      .into_iter()
+      .filter_map(|member| member.name.as_deref())
+      .collect::<Vec<_>>();
+    assert_eq!(methods, vec!["new", "parse", "position"]);
+    assert!(all_inherent_items(&index).len() >= methods.len());
+
+    let traits = implemented_traits(parser, &index, false);
+    assert_eq!(
+      traits,
+      vec!["Debug", "Clone", "Default", "From<&'a str>", "Visitor"]
+    );
+    let all = implemented_traits(parser, &index, true);
+    assert!(all.contains(&"Send".to_string()));
+    assert!(all.contains(&"Into<U>".to_string()));
+  }
+
+  #[test]
+  fn test_process_docs_implementations() {
+    let parser_content = |options: &ProcessOptions| {
+      let docs = process(options);
+      assert!(!titles(&docs).contains(&"new"));
+      session(&docs, "demo::parser::Parser").1.content.clone()
+    };
+
+    let content = parser_content(&ProcessOptions::default());
+    let implementations = content.find("\n# Implementations\n").unwrap();
+    let traits = content.find("\n# Trait Implementations\n").unwrap();
+    assert!(implementations < traits);
+    for name in ["new", "parse", "position"] {
+      let signature = content.find(&format!("pub fn {name}(")).unwrap();
+      assert!(implementations < signature && signature < traits);
+    }
+    assert!(content[traits..].contains("`Clone`, `Default`"));
+    assert!(content[traits..].contains("`Send`"));
+
+    let content = parser_content(
+      &ProcessOptions::builder()
+        .include_blanket_impls(false)
+        .build(),
+    );
+    assert!(content.contains("`From<&'a str>`"));
+    assert!(!content.contains("`Send`"));
+    assert!(!content.contains("`Into<U>`"));
+  }
+}
@@ -18,14 +18,19 @@
 //!
 //! ```no_run
 //! use crates_llms_txt::CrateDocs;
+//! use crates_llms_txt::fetch_docs::OnlineDocs;
+//! use crates_llms_txt::options::ProcessOptions;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let online = OnlineDocs::default();
+//!     let options = ProcessOptions::default();
+//!
 //!     // Fetch latest version
-//!     let docs = CrateDocs::from_online("serde", None).await?;
+//!     let docs = CrateDocs::from_online("serde", None, &online, &options).await?;
 //!     
 //!     // Fetch specific version
-//!     let docs = CrateDocs::from_online("clap", Some("4.5.39".to_string())).await?;
+//!     let docs = CrateDocs::from_online("clap", Some("4.5.39".to_string()), &online, &options).await?;
 //!     
 //!     println!("Found {} documentation items", docs.sessions.len());
 //!     Ok(())
@@ -37,11 +42,18 @@
 //! ```no_run
 //! use std::path::PathBuf;
 //! use crates_llms_txt::CrateDocs;
+//! use crates_llms_txt::options::ProcessOptions;
+//!
+//! // Include public items without doc comments as well
+//! let options = ProcessOptions::builder()
+//!     .include_undocumented(true)
+//!     .build();
 //!
 //! // Generate docs with all features
 //! let docs = CrateDocs::from_local(
 //!     PathBuf::from("./Cargo.toml"),
 //!     Some("stable".to_string()),
+//!     &options,
 //! )?;
 //!
 //! // Generate docs with specific features
@@ -50,52 +62,117 @@
 //!     false, // don't disable default features
 //!     Some(vec!["async".to_string()]),
 //!     None, // auto-detect toolchain
+//!     &options,
 //! )?;
 //! ```
 
+use std::collections::HashMap;
+use std::path::Path;
 #[cfg(feature = "rustdoc")]
 use std::path::PathBuf;
 
 use fetch_docs::OnlineDocs;
-use rustdoc_types::Visibility;
+use rustdoc_types::{Deprecation, Id, ItemEnum, ItemKind, Visibility};
 use serde::{Deserialize, Serialize};
 
+use attrs::{Attr, ItemAttributes};
 use error::{Error, Result};
+use options::{DeprecatedItems, ProcessOptions};
+use sections::{ItemCategory, Section};
 use temp_trait::CommonCrates;
+use warnings::{Warning, WarningKind};
 
+pub mod attrs;
+pub mod cache;
 pub mod error;
 pub mod fetch_docs;
+mod fields;
 #[cfg(feature = "rustdoc")]
 mod gen_docs;
+mod impls;
+mod links;
+mod macros;
+mod markdown;
+pub mod options;
+mod reexports;
+pub mod render;
+pub mod sections;
+pub mod signature;
 pub mod temp_trait;
-
-/// Base URL for docs.rs crate documentation API endpoints
-const DOCS_BASE_URL: &str = "https://docs.rs/crate";
+mod traits;
+pub mod urls;
+pub mod versions;
+pub mod warnings;
 
 /// Represents a single documentation session item with metadata.
 ///
 /// This structure contains basic information about a documentation item,
-/// including its title, description, and link to the source.
+/// including its title, description, and links to its docs.rs page and source.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionItem {
-  /// The title or name of the documentation item
+  /// The fully qualified path of the item (e.g. `clap::builder::Arg`), or its
+  /// name if the item has no path of its own
   pub title: String,
-  /// A brief description of the item (currently unused, reserved for future use)
+  /// A brief description of the item, taken from the first paragraph of its docs
   pub description: String,
-  /// Direct link to the documentation source on docs.rs or local server
+  /// Link to the rendered docs.rs page of the item, or to its source view if
+  /// the item has no page of its own
   pub link: String,
+  /// Link to the source view of the item on docs.rs, anchored at its first line
+  pub source_link: Option<String>,
+  /// Coarse category of the item, used to group sessions into sections
+  pub category: ItemCategory,
+  /// Path of the module the item is listed under (e.g. `clap::builder`), or
+  /// an empty string for items without a path of their own
+  pub module: String,
+  /// Whether the item has no doc comment; only set when undocumented items
+  /// are included through `ProcessOptions::include_undocumented`
+  #[serde(default)]
+  pub undocumented: bool,
+  /// `#[must_use]`, `#[non_exhaustive]` and `#[cfg]` gates of the item
+  #[serde(default)]
+  pub attributes: ItemAttributes,
+  /// Cargo features that must be enabled for the item to exist, taken from
+  /// its `#[cfg]` and `#[doc(cfg)]` attributes
+  #[serde(default)]
+  pub required_features: Vec<String>,
+  /// Deprecation of the item, with the version it was deprecated in and the
+  /// reason given, or `None` if it is not deprecated
+  #[serde(default)]
+  pub deprecation: Option<Deprecation>,
+  /// Whether the item is listed in the "Optional" section whatever its
+  /// category, e.g. deprecated items with `DeprecatedItems::Optional`
+  #[serde(default)]
+  pub optional: bool,
 }
 
 /// Represents a full documentation session item with complete content.
 ///
 /// This structure contains the full documentation content along with
-/// the link to its source location.
+/// links to its docs.rs page and source location.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FullSessionItem {
   /// The complete documentation content as a string
   pub content: String,
-  /// Direct link to the documentation source
+  /// Link to the rendered docs.rs page of the item
   pub link: String,
+  /// Link to the source view of the item on docs.rs, anchored at its first line
+  pub source_link: Option<String>,
+  /// Whether the item has no doc comment, in which case the content is its
+  /// signature only
+  #[serde(default)]
+  pub undocumented: bool,
+  /// `#[must_use]`, `#[non_exhaustive]` and `#[cfg]` gates of the item
+  #[serde(default)]
+  pub attributes: ItemAttributes,
+  /// Cargo features that must be enabled for the item to exist, taken from
+  /// its `#[cfg]` and `#[doc(cfg)]` attributes
+  #[serde(default)]
+  pub required_features: Vec<String>,
+  /// Deprecation of the item, with the version it was deprecated in and the
+  /// reason given, or `None` if it is not deprecated
+  #[serde(default)]
+  pub deprecation: Option<Deprecation>,
 }
 
 /// Main structure containing all documentation data for a crate.
@@ -113,6 +190,10 @@ pub struct CrateDocs {
   pub sessions: Vec<SessionItem>,
   /// Full documentation items containing complete content
   pub full_sessions: Vec<FullSessionItem>,
+  /// Non-fatal problems encountered while processing, e.g. items without a
+  /// source span that were given a fallback link
+  #[serde(default)]
+  pub warnings: Vec<Warning>,
 }
 
 impl CrateDocs {
@@ -143,9 +224,100 @@ impl CrateDocs {
       version: version.to_string(),
       sessions: Vec::new(),
       full_sessions: Vec::new(),
+      warnings: Vec::new(),
     }
   }
 
+  /// Group the sessions into the sections of the `llms.txt` index.
+  ///
+  /// Sections are ordered by module hierarchy and, within a module, by item
+  /// category (modules, structs, enums, traits, functions, macros, type aliases
+  /// and constants). Low-priority items, such as items without a module of
+  /// their own, are collected in a trailing "Optional" section.
+  ///
+  /// # Returns
+  ///
+  /// * `Vec<Section>` - The grouped sessions
+  pub fn sections(&self) -> Vec<Section> {
+    sections::group_sessions(&self.sessions)
+  }
+
+  /// Render the documentation as an `llms.txt` index.
+  ///
+  /// The output follows the llms.txt specification: an H1 title with the crate
+  /// name, a blockquote summary and one H2 section per group returned by
+  /// [`CrateDocs::sections`], listing each item as a
+  /// `- [title](link): description` line.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use crates_llms_txt::CrateDocs;
+  ///
+  /// let docs = CrateDocs::new("my_crate", "1.0.0");
+  /// assert!(docs.to_llms_txt().starts_with("# my_crate"));
+  /// ```
+  pub fn to_llms_txt(&self) -> String {
+    render::llms_txt(self)
+  }
+
+  /// Render the documentation as an `llms-full.txt` document.
+  ///
+  /// The output contains the same header as `llms.txt`, followed by the
+  /// complete documentation content of every item.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use crates_llms_txt::CrateDocs;
+  ///
+  /// let docs = CrateDocs::new("my_crate", "1.0.0");
+  /// assert!(docs.to_llms_full_txt().starts_with("# my_crate"));
+  /// ```
+  pub fn to_llms_full_txt(&self) -> String {
+    render::llms_full_txt(self)
+  }
+
+  /// Write `llms.txt` and `llms-full.txt` into the given directory.
+  ///
+  /// The directory is created if needed and existing files are overwritten.
+  ///
+  /// # Arguments
+  ///
+  /// * `dir` - The output directory
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Vec<PathBuf>>` - Paths of the written files
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Io` - If the directory or one of the files cannot be written
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::fetch_docs::OnlineDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
+  ///
+  /// #[tokio::main]
+  /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///     let online = OnlineDocs::default();
+  ///     let options = ProcessOptions::default();
+  ///     let docs =
+  ///         CrateDocs::from_online("serde", None, &online, &options).await?;
+  ///     docs.write_to_dir("./docs")?;
+  ///     Ok(())
+  /// }
+  /// ```
+  pub fn write_to_dir<P: AsRef<Path>>(
+    &self,
+    dir: P,
+  ) -> Result<Vec<std::path::PathBuf>> {
+    render::write_to_dir(self, dir.as_ref())
+  }
+
   /// Process raw documentation data into structured `CrateDocs` format.
   ///
   /// This internal method converts rustdoc JSON data into the structured format
@@ -157,6 +329,7 @@ impl CrateDocs {
   /// * `lib_name` - The name of the crate
   /// * `docs` - Raw documentation data implementing the `CommonCrates` trait
   /// * `version` - Optional version string; if None, uses the version from docs
+  /// * `options` - Options controlling which items are included
   ///
   /// # Returns
   ///
@@ -165,53 +338,395 @@ impl CrateDocs {
   /// # Errors
   ///
   /// Returns an error if the documentation data cannot be processed or if
-  /// required fields are missing from the rustdoc JSON.
+  /// required fields are missing from the rustdoc JSON; `Error::Config` if
+  /// no version is given and the documentation does not record one.
   fn process_docs<T: CommonCrates>(
     lib_name: &str,
     docs: T,
     version: Option<String>,
+    options: &ProcessOptions,
   ) -> Result<CrateDocs> {
-    let version = version.unwrap_or_else(|| docs.crate_version());
+    let Some(version) = version.or_else(|| docs.crate_version()) else {
+      return Err(Error::Config(format!(
+        "the documentation of {lib_name} does not record its version"
+      )));
+    };
     let mut crate_docs = CrateDocs::new(lib_name, &version);
-    let base_url =
-      format!("{}/{}/{}/source", DOCS_BASE_URL, &lib_name, version);
+    let index = docs.index();
+    let mut paths = docs.paths();
 
-    // Add the main crate entry
+    // List items under their public path, which differs from the declaration
+    // path for items re-exported from private modules
+    let public_paths = reexports::public_paths(&docs.root_id(), &index, &paths);
+    for (id, path) in &public_paths {
+      if let Some(summary) = paths.get_mut(id) {
+        summary.path = path.clone();
+      }
+    }
+    let member_paths = reexports::member_paths(&index, &paths);
+
+    // Intra-doc links are rewritten to absolute URLs in the full content
+    let member_parents = reexports::member_parents(&index, &paths);
+    let external_crates = docs.external_crates();
+    let link_resolver = links::LinkResolver::new(
+      lib_name,
+      &version,
+      &index,
+      &paths,
+      &member_parents,
+      &external_crates,
+    );
+
+    // Add the main crate entry, summarized from the crate-level docs in
+    // `llms.txt` and with the complete crate-level docs in `llms-full.txt`
+    let root = index.get(&docs.root_id());
+    let root_docs = root.and_then(|root| root.docs.as_deref());
+    let root_description =
+      root_docs.map(markdown::summarize).unwrap_or_default();
+    let root_module = paths
+      .get(&docs.root_id())
+      .map(sections::parent_module)
+      .unwrap_or_else(|| lib_name.replace('-', "_"));
     crate_docs.sessions.push(SessionItem {
       title: lib_name.to_string(),
-      description: "".to_string(),
-      link: format!("https://docs.rs/{lib_name}/{version}"),
+      description: root_description,
+      link: urls::crate_url(lib_name, &version),
+      source_link: None,
+      category: ItemCategory::Module,
+      module: root_module,
+      undocumented: false,
+      attributes: ItemAttributes::default(),
+      required_features: Vec::new(),
+      deprecation: None,
+      optional: false,
+    });
+    crate_docs.full_sessions.push(FullSessionItem {
+      content: match (root, root_docs) {
+        (Some(root), Some(root_docs)) => {
+          markdown::resolve_links(root_docs, &link_resolver.links(root))
+        }
+        _ => String::new(),
+      },
+      link: urls::crate_url(lib_name, &version),
+      source_link: root
+        .and_then(|root| root.span.as_ref())
+        .map(|span| urls::source_url(lib_name, &version, span)),
+      undocumented: root_docs.is_none(),
+      attributes: ItemAttributes::default(),
+      required_features: Vec::new(),
+      deprecation: None,
     });
 
-    // Process all documentation items from the crate index
-    for (_, item) in docs.index() {
-      if let Some(docs_content) = item.docs {
-        // Only include public items to avoid exposing private implementation details
-        if item.visibility != Visibility::Public {
-          continue;
+    // Filters shared by sessions and by the members listed in their content
+    let filtered_out = |item: &temp_trait::Item,
+                        item_attrs: &[Attr],
+                        path: Option<&Vec<String>>| {
+      let cfg = ItemAttributes::from_attrs(item_attrs).cfg;
+      (item.deprecation.is_some()
+        && options.deprecated_items == DeprecatedItems::Exclude)
+        || (attrs::is_hidden(item_attrs) && !options.include_hidden)
+        || !options.allows_features(&attrs::required_features(&cfg))
+        // Items without a path cannot match `include_paths`
+        || match path {
+          Some(path) => !options.allows_path(&path.join("::")),
+          None => !options.include_paths.is_empty(),
         }
+    };
+    let member_allowed = |member: &temp_trait::Item| {
+      // Variants, their fields and trait items share the visibility of their
+      // parent
+      let visible =
+        matches!(member.visibility, Visibility::Public | Visibility::Default)
+          || (options.includes_private
+            && matches!(
+              member.visibility,
+              Visibility::Crate | Visibility::Restricted { .. }
+            ));
+      visible
+        && !filtered_out(
+          member,
+          &attrs::parse_attrs(&member.attrs),
+          member_paths.get(&member.id),
+        )
+    };
+    let member_included = |member: &temp_trait::Item| {
+      member_allowed(member)
+        && (member.docs.is_some() || options.include_undocumented)
+    };
+    let inherent_items = impls::all_inherent_items(&index);
 
-        // Extract filename from span information for source links
-        let filename = item.span.unwrap().filename;
-        let link = format!("{}/{}", base_url, filename.to_str().unwrap());
-
-        // Create session item with appropriate title
-        crate_docs.sessions.push(SessionItem {
-          title: match item.name {
-            Some(name) => name,
-            None => filename.to_str().unwrap().to_string(),
-          },
-          description: "".to_string(),
-          link: link.clone(),
-        });
-
-        // Store full documentation content
-        crate_docs.full_sessions.push(FullSessionItem {
-          content: docs_content,
-          link,
-        });
+    // Process all documentation items from the crate index. The index is a
+    // `HashMap`, so entries are collected first and sorted afterwards to keep
+    // the output stable between runs
+    let mut entries = Vec::new();
+    for item in index.values() {
+      // Re-exports are resolved above, their targets are listed instead
+      if matches!(item.inner, ItemEnum::Use(_)) {
+        continue;
+      }
+
+      // Module-level items are part of the public API when they can be
+      // reached from the crate root, whatever their declared visibility;
+      // other items must be public themselves. Private items are only listed
+      // on request
+      let module_level = paths.get(&item.id).is_some_and(|summary| {
+        summary.crate_id == 0 && summary.kind != ItemKind::Variant
+      });
+      let public = if module_level {
+        public_paths.contains_key(&item.id)
+      } else {
+        item.visibility == Visibility::Public
       };
+      let private = matches!(
+        item.visibility,
+        Visibility::Crate | Visibility::Restricted { .. }
+      ) || (module_level && !public);
+      if !public && !(options.includes_private && private) {
+        continue;
+      }
+
+      // The crate root has already been added as the first session, and
+      // fields, variants and associated items of inherent impls are listed
+      // under their type
+      if item.id == docs.root_id()
+        || inherent_items.contains(&item.id)
+        || matches!(item.inner, ItemEnum::StructField(_) | ItemEnum::Variant(_))
+      {
+        continue;
+      }
+
+      let item_attrs = attrs::parse_attrs(&item.attrs);
+      let item_path = paths
+        .get(&item.id)
+        .map(|summary| &summary.path)
+        .or_else(|| member_paths.get(&item.id));
+      if !options.allows_kind(sections::item_kind(&item.inner))
+        || filtered_out(item, &item_attrs, item_path)
+      {
+        continue;
+      }
+      let attributes = ItemAttributes::from_attrs(&item_attrs);
+      let required_features = attrs::required_features(&attributes.cfg);
+
+      // Below its documentation, an item lists its members as on its rustdoc
+      // page: fields or variants and the associated items of inherent impls
+      // for types, associated items grouped into required and provided ones
+      // for traits. The fields of a variant are listed below it
+      let mut page_sections = Vec::new();
+      let nested = fields::fields(item, &index)
+        .into_iter()
+        .chain(fields::variants(item, &index))
+        .filter(|member| member_included(member))
+        .map(|member| {
+          let mut block = member_block(member, &index, &link_resolver);
+          for field in fields::fields(member, &index) {
+            if member_included(field) {
+              block.push_str("\n\n");
+              block.push_str(&list_item(&member_block(
+                field,
+                &index,
+                &link_resolver,
+              )));
+            }
+          }
+          block
+        })
+        .collect::<Vec<_>>();
+      if !nested.is_empty() {
+        let heading = match item.inner {
+          ItemEnum::Enum(_) => "Variants",
+          _ => "Fields",
+        };
+        page_sections.push((heading, nested.join("\n\n")));
+      }
+      // Every item of a trait is part of the contract of its implementors,
+      // documented or not
+      for (heading, members) in traits::trait_item_groups(item, &index) {
+        let members = members
+          .into_iter()
+          .filter(|member| member_allowed(member))
+          .map(|member| member_block(member, &index, &link_resolver))
+          .collect::<Vec<_>>();
+        if !members.is_empty() {
+          page_sections.push((heading, members.join("\n\n")));
+        }
+      }
+      let members = impls::inherent_items(item, &index)
+        .into_iter()
+        .filter(|member| member_included(member))
+        .map(|member| member_block(member, &index, &link_resolver))
+        .collect::<Vec<_>>();
+      if !members.is_empty() {
+        page_sections.push(("Implementations", members.join("\n\n")));
+      }
+      let has_members = !page_sections.is_empty();
+
+      // Macros list their kind and how they are invoked first
+      if let Some(kind) = macros::macro_kind(item) {
+        page_sections.insert(0, ("Macro Kind", kind));
+      }
+
+      // Then the traits it builds on or implements, and the impls of a trait
+      // found in the crate
+      let supertraits = traits::supertraits(item);
+      if !supertraits.is_empty() {
+        page_sections.insert(0, ("Supertraits", code_list(&supertraits)));
+      }
+      let implemented_traits =
+        impls::implemented_traits(item, &index, options.include_blanket_impls);
+      if !implemented_traits.is_empty() {
+        page_sections
+          .push(("Trait Implementations", code_list(&implemented_traits)));
+      }
+      let implementors = traits::implementors(item, &index);
+      if !implementors.is_empty() {
+        page_sections.push((
+          "Implementors",
+          format!("```rust\n{}\n```", implementors.join("\n")),
+        ));
+      }
+
+      // Undocumented items are described by their signature alone.
+      // Undocumented types and traits are still listed for their members
+      let signature = signature::item_signature(item, &index);
+      let docs_content = match &item.docs {
+        Some(docs_content) => docs_content.as_str(),
+        None if has_members => "",
+        None if options.include_undocumented && signature.is_some() => "",
+        None => continue,
+      };
+
+      // Title the session with its canonical path, e.g. `clap::builder::Arg`,
+      // falling back to the bare name for items without a path (trait
+      // items), then to the source file and finally to the item id
+      let mut item_warnings = Vec::new();
+      let title = match (paths.get(&item.id), &item.name, &item.span) {
+        (Some(summary), _, _) => summary.path.join("::"),
+        (None, Some(name), _) => name.clone(),
+        (None, None, Some(span)) => {
+          span.filename.to_string_lossy().into_owned()
+        }
+        (None, None, None) => {
+          item_warnings.push((
+            WarningKind::MissingTitle,
+            "item has no path or name; titled by its id".to_string(),
+          ));
+          format!("item {}", item.id.0)
+        }
+      };
+
+      // Link to the rendered docs.rs page, keeping the source view as a
+      // secondary link for items that have one
+      let source_link = match &item.span {
+        Some(span) => {
+          if span.filename.to_str().is_none() {
+            item_warnings.push((
+              WarningKind::NonUtf8Path,
+              format!(
+                "source file name is not valid UTF-8: {}",
+                span.filename.to_string_lossy()
+              ),
+            ));
+          }
+          Some(urls::source_url(lib_name, &version, span))
+        }
+        None => {
+          item_warnings.push((
+            WarningKind::MissingSpan,
+            "item has no source span; no source link".to_string(),
+          ));
+          None
+        }
+      };
+      let item_link = paths
+        .get(&item.id)
+        .and_then(|summary| urls::item_url(lib_name, &version, summary));
+      let link = match (item_link, &source_link) {
+        (Some(link), _) => link,
+        (None, Some(source_link)) => source_link.clone(),
+        (None, None) => {
+          item_warnings.push((
+            WarningKind::MissingLink,
+            "item has no docs.rs page or source view; linked to the crate"
+              .to_string(),
+          ));
+          urls::crate_url(lib_name, &version)
+        }
+      };
+      // Reported once the item is known to be in the output
+      let item_warnings = item_warnings
+        .into_iter()
+        .map(|(kind, message)| Warning::new(kind, item.id.0, &title, message))
+        .collect::<Vec<_>>();
+      let (category, module) = match paths.get(&item.id) {
+        Some(summary) => (
+          ItemCategory::from_kind(&summary.kind),
+          sections::parent_module(summary),
+        ),
+        None => (ItemCategory::Other, String::new()),
+      };
+
+      let session = SessionItem {
+        title,
+        description: markdown::summarize(docs_content),
+        link: link.clone(),
+        source_link: source_link.clone(),
+        category,
+        module,
+        undocumented: item.docs.is_none(),
+        attributes: attributes.clone(),
+        required_features: required_features.clone(),
+        deprecation: item.deprecation.clone(),
+        optional: item.deprecation.is_some()
+          && options.deprecated_items == DeprecatedItems::Optional,
+      };
+
+      // Store full documentation content, led by the item's declaration and
+      // the attributes rustdoc shows above it
+      let signature = signature.map(|signature| {
+        format!("{}{signature}", attributes.signature_prefix())
+      });
+      let docs_content =
+        markdown::resolve_links(docs_content, &link_resolver.links(item));
+      let mut content = match (signature, docs_content.is_empty()) {
+        (Some(signature), false) => {
+          format!("```rust\n{signature}\n```\n\n{docs_content}")
+        }
+        (Some(signature), true) => format!("```rust\n{signature}\n```"),
+        (None, _) => docs_content,
+      };
+      for (heading, body) in page_sections {
+        content.push_str(&format!("\n\n# {heading}\n\n{body}"));
+      }
+      let full_session = FullSessionItem {
+        content,
+        link,
+        source_link,
+        undocumented: item.docs.is_none(),
+        attributes,
+        required_features,
+        deprecation: item.deprecation.clone(),
+      };
+
+      entries.push((item.id, session, full_session, item_warnings));
+    }
+
+    // Module tree order, then category and path; the item id only breaks ties
+    // between otherwise identical entries
+    entries.sort_by(|(a_id, a, _, _), (b_id, b, _, _)| {
+      sections::session_order(a, b).then(a_id.cmp(b_id))
+    });
+    if let Some(max_items) = options.max_items {
+      entries.truncate(max_items);
+    }
+    for (_, session, full_session, warnings) in entries {
+      crate_docs.sessions.push(session);
+      crate_docs.full_sessions.push(full_session);
+      crate_docs.warnings.extend(warnings);
     }
+    crate_docs
+      .warnings
+      .sort_by(|a, b| (&a.item, a.item_id).cmp(&(&b.item, b.item_id)));
 
     Ok(crate_docs)
   }
@@ -220,13 +735,17 @@ impl CrateDocs {
   ///
   /// This method downloads and processes rustdoc JSON data from docs.rs,
   /// automatically handling zstd compression and version compatibility issues.
-  /// It attempts to parse using the standard rustdoc format first, then falls
-  /// back to an internal format if needed.
+  /// The JSON is downloaded once and parsed with the deserializer for its
+  /// `format_version`. `latest` and semver requirements are resolved to an
+  /// exact version first, so `CrateDocs::version` is always concrete.
   ///
   /// # Arguments
   ///
   /// * `lib_name` - The name of the crate (e.g., "serde", "clap")
-  /// * `version` - Optional version string; if None, fetches "latest"
+  /// * `version` - Optional version or semver requirement (e.g. "4.5.39",
+  ///   "~4.5"); if None, fetches "latest"
+  /// * `online` - The client the documentation is downloaded with
+  /// * `options` - Options controlling which items are included
   ///
   /// # Returns
   ///
@@ -237,20 +756,30 @@ impl CrateDocs {
   /// * `Error::Network` - If the HTTP request to docs.rs fails
   /// * `Error::Json` - If the JSON data cannot be parsed
   /// * `Error::Io` - If decompression fails
-  /// * `Error::Config` - If the rustdoc format is incompatible
+  /// * `Error::Config` - If the rustdoc format version is not supported, or
+  ///   no release matches the requested version
   ///
   /// # Examples
   ///
   /// ```no_run
+  /// use std::time::Duration;
+  ///
   /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::fetch_docs::OnlineDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
   ///
   /// #[tokio::main]
   /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///     let online = OnlineDocs::builder()
+  ///         .timeout(Duration::from_secs(120))
+  ///         .build()?;
+  ///     let options = ProcessOptions::default();
+  ///
   ///     // Fetch latest version
-  ///     let docs = CrateDocs::from_online("serde", None).await?;
+  ///     let docs = CrateDocs::from_online("serde", None, &online, &options).await?;
   ///     
   ///     // Fetch specific version
-  ///     let docs = CrateDocs::from_online("clap", Some("4.5.39".to_string())).await?;
+  ///     let docs = CrateDocs::from_online("clap", Some("4.5.39".to_string()), &online, &options).await?;
   ///     
   ///     println!("Crate: {} v{}", docs.lib_name, docs.version);
   ///     println!("Documentation items: {}", docs.sessions.len());
@@ -261,27 +790,70 @@ impl CrateDocs {
   pub async fn from_online(
     lib_name: &str,
     version: Option<String>,
+    online: &OnlineDocs,
+    options: &ProcessOptions,
   ) -> Result<CrateDocs> {
-    let version_str = version.unwrap_or("latest".to_string());
-    let url = format!("{DOCS_BASE_URL}/{lib_name}/{version_str}/json");
-
-    // Try parsing with standard rustdoc_types::Crate first for maximum compatibility
-    match OnlineDocs::fetch_json::<rustdoc_types::Crate>(url.as_str()).await {
-      Ok(result) => {
-        let crate_version = Some(result.crate_version());
-        CrateDocs::process_docs(lib_name, result, crate_version)
-      }
-      Err(_) => {
-        // Fallback to internal Crate type for older or incompatible formats
-        match OnlineDocs::fetch_json::<temp_trait::Crate>(url.as_str()).await {
-          Ok(result) => {
-            let crate_version = Some(result.crate_version());
-            CrateDocs::process_docs(lib_name, result, crate_version)
-          }
-          Err(err) => Err(err),
-        }
-      }
-    }
+    let docs = online.fetch_docs(lib_name, version).await?;
+    CrateDocs::process_docs(lib_name, docs, None, options)
+  }
+
+  /// Fetch crate documentation from docs.rs through an on-disk cache.
+  ///
+  /// Behaves like `from_online`, but reuses documents kept in `cache` and
+  /// stores the ones it downloads; see `OnlineDocs::fetch_docs_cached`.
+  ///
+  /// # Arguments
+  ///
+  /// * `lib_name` - The name of the crate (e.g., "serde", "clap")
+  /// * `version` - Optional version or semver requirement (e.g. "4.5.39",
+  ///   "~4.5"); if None, fetches "latest"
+  /// * `online` - The client the documentation is downloaded with
+  /// * `cache` - The cache to read from and store into
+  /// * `options` - Options controlling which items are included
+  ///
+  /// # Returns
+  ///
+  /// * `Result<CrateDocs>` - Structured documentation data
+  ///
+  /// # Errors
+  ///
+  /// * `Error::Network` - If the HTTP request to docs.rs fails
+  /// * `Error::Json` - If the JSON data cannot be parsed
+  /// * `Error::Io` - If the cache cannot be read or written
+  /// * `Error::Config` - If the rustdoc format version is not supported, no
+  ///   release matches the requested version, or the document is not cached
+  ///   in offline mode
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::cache::DocsCache;
+  /// use crates_llms_txt::fetch_docs::OnlineDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
+  ///
+  /// #[tokio::main]
+  /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///     let online = OnlineDocs::default();
+  ///     let cache = DocsCache::new("./.docs-cache");
+  ///     let options = ProcessOptions::default();
+  ///
+  ///     let docs =
+  ///         CrateDocs::from_online_cached("serde", None, &online, &cache, &options)
+  ///             .await?;
+  ///     Ok(())
+  /// }
+  /// ```
+  ///
+  pub async fn from_online_cached(
+    lib_name: &str,
+    version: Option<String>,
+    online: &OnlineDocs,
+    cache: &cache::DocsCache,
+    options: &ProcessOptions,
+  ) -> Result<CrateDocs> {
+    let docs = online.fetch_docs_cached(lib_name, version, cache).await?;
+    CrateDocs::process_docs(lib_name, docs, None, options)
   }
 
   /// Fetch crate documentation from a custom URL endpoint.
@@ -293,6 +865,9 @@ impl CrateDocs {
   /// # Arguments
   ///
   /// * `url` - Complete URL to the rustdoc JSON endpoint
+  /// * `online` - The client the documentation is downloaded with, e.g. one
+  ///   carrying the credentials of a private server
+  /// * `options` - Options controlling which items are included
   ///
   /// # Returns
   ///
@@ -302,63 +877,55 @@ impl CrateDocs {
   ///
   /// * `Error::Network` - If the HTTP request fails
   /// * `Error::Json` - If the JSON data cannot be parsed
-  /// * `Error::Config` - If the crate name cannot be extracted from the data
+  /// * `Error::Io` - If decompression fails
+  /// * `Error::Config` - If the rustdoc format version is not supported, the
+  ///   crate name cannot be extracted from the data or its version cannot be
+  ///   resolved
   ///
   /// # Examples
   ///
   /// ```no_run
   /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::fetch_docs::OnlineDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
   ///
   /// #[tokio::main]
   /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  ///     let options = ProcessOptions::default();
+  ///
   ///     // Fetch from docs.rs
   ///     let docs = CrateDocs::from_url(
-  ///         "https://docs.rs/crate/clap/latest/json"
+  ///         "https://docs.rs/crate/clap/latest/json",
+  ///         &OnlineDocs::default(),
+  ///         &options,
   ///     ).await?;
   ///     
   ///     // Fetch from custom server
+  ///     let online = OnlineDocs::builder()
+  ///         .bearer_auth("my-docs.example.com", "secret-token")
+  ///         .build()?;
   ///     let docs = CrateDocs::from_url(
-  ///         "https://my-docs.example.com/crate/my-crate/1.0.0/json"
+  ///         "https://my-docs.example.com/crate/my-crate/1.0.0/json",
+  ///         &online,
+  ///         &options,
   ///     ).await?;
   ///     
   ///     Ok(())
   /// }
   /// ```
   ///
-  pub async fn from_url(url: &str) -> Result<CrateDocs> {
-    // Attempt to parse with standard rustdoc format first
-    match OnlineDocs::fetch_json::<rustdoc_types::Crate>(url).await {
-      Ok(docs) => {
-        let root_id = docs.root_id();
-        if let Some(root_item) = docs.index().get(&root_id) {
-          let lib_name =
-            &root_item.name.clone().unwrap_or("unknown".to_string());
-          let crate_version = Some(docs.crate_version());
-          return CrateDocs::process_docs(lib_name, docs, crate_version);
-        }
-        Err(Error::Config(
-          "Failed to extract crate name from root item".into(),
-        ))
-      }
-      Err(_) => {
-        // Fallback to internal format for compatibility
-        match OnlineDocs::fetch_json::<temp_trait::Crate>(url).await {
-          Ok(docs) => {
-            let root_id = docs.root_id();
-            if let Some(root_item) = docs.index().get(&root_id) {
-              let lib_name =
-                &root_item.name.clone().unwrap_or("unknown".to_string());
-              let crate_version = Some(docs.crate_version());
-              return CrateDocs::process_docs(lib_name, docs, crate_version);
-            }
-            Err(Error::Config(
-              "Failed to extract crate name from root item".into(),
-            ))
-          }
-          Err(err) => Err(err),
-        }
-      }
-    }
+  pub async fn from_url(
+    url: &str,
+    online: &OnlineDocs,
+    options: &ProcessOptions,
+  ) -> Result<CrateDocs> {
+    let docs = online.fetch_docs_by_url(url).await?;
+    let Some(lib_name) = docs.root_name() else {
+      return Err(Error::Config(
+        "Failed to extract crate name from root item".into(),
+      ));
+    };
+    CrateDocs::process_docs(&lib_name, docs, None, options)
   }
 
   /// Generate documentation for a local crate with all features enabled.
@@ -372,6 +939,7 @@ impl CrateDocs {
   /// * `manifest_path` - Path to the Cargo.toml file of the target crate
   /// * `toolchain` - Optional Rust toolchain ("stable", "nightly", etc.)
   ///   If None, auto-detects the appropriate toolchain
+  /// * `options` - Options controlling which items are included
   ///
   /// # Returns
   ///
@@ -389,17 +957,22 @@ impl CrateDocs {
   /// ```no_run
   /// use std::path::PathBuf;
   /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
+  ///
+  /// let options = ProcessOptions::default();
   ///
   /// // Generate with specific toolchain
   /// let docs = CrateDocs::from_local(
   ///     PathBuf::from("./Cargo.toml"),
   ///     Some("stable".to_string()),
+  ///     &options,
   /// )?;
   ///
   /// // Auto-detect toolchain
   /// let docs = CrateDocs::from_local(
   ///     PathBuf::from("./my-project/Cargo.toml"),
   ///     None,
+  ///     &options,
   /// )?;
   ///
   /// println!("Generated docs for: {} v{}", docs.lib_name, docs.version);
@@ -408,6 +981,7 @@ impl CrateDocs {
   pub fn from_local(
     manifest_path: PathBuf,
     toolchain: Option<String>,
+    options: &ProcessOptions,
   ) -> Result<CrateDocs> {
     let gen_docs_struct = match toolchain {
       Some(toolchain) => {
@@ -420,7 +994,7 @@ impl CrateDocs {
 
     let lib_name = gen_docs_struct.lib_name;
     let docs = gen_docs_struct.docs;
-    CrateDocs::process_docs(&lib_name, docs, None)
+    CrateDocs::process_docs(&lib_name, docs, None, options)
   }
 
   /// Generate documentation for a local crate with custom feature configuration.
@@ -435,6 +1009,7 @@ impl CrateDocs {
   /// * `no_default_features` - If true, disables all default features
   /// * `features` - Optional list of specific features to enable
   /// * `toolchain` - Optional Rust toolchain; if None, auto-detects
+  /// * `options` - Options controlling which items are included
   ///
   /// # Returns
   ///
@@ -452,6 +1027,9 @@ impl CrateDocs {
   /// ```no_run
   /// use std::path::PathBuf;
   /// use crates_llms_txt::CrateDocs;
+  /// use crates_llms_txt::options::ProcessOptions;
+  ///
+  /// let options = ProcessOptions::default();
   ///
   /// // Generate with specific features only
   /// let docs = CrateDocs::from_local_with_features(
@@ -459,6 +1037,7 @@ impl CrateDocs {
   ///     true, // disable default features
   ///     Some(vec!["async".to_string(), "json".to_string()]),
   ///     Some("stable".to_string()),
+  ///     &options,
   /// )?;
   ///
   /// // Generate with default features plus additional ones
@@ -467,6 +1046,7 @@ impl CrateDocs {
   ///     false, // keep default features
   ///     Some(vec!["experimental".to_string()]),
   ///     None, // auto-detect toolchain
+  ///     &options,
   /// )?;
   /// ```
   #[cfg(feature = "rustdoc")]
@@ -475,6 +1055,7 @@ impl CrateDocs {
     no_default_features: bool,
     features: Option<Vec<String>>,
     toolchain: Option<String>,
+    options: &ProcessOptions,
   ) -> Result<CrateDocs> {
     let gen_docs_struct = match toolchain {
       Some(toolchain) => gen_docs::gen_docs_with_features(
@@ -492,21 +1073,490 @@ impl CrateDocs {
 
     let lib_name = gen_docs_struct.lib_name;
     let docs = gen_docs_struct.docs;
-    CrateDocs::process_docs(&lib_name, docs, None)
+    CrateDocs::process_docs(&lib_name, docs, None, options)
   }
 }
 
+/// Render a member listed in the content of its parent: its declaration,
+/// deprecation notice and documentation.
+///
+/// Fields and variants are declared inline, e.g. `value: i64`, as on rustdoc
+/// pages; other members get a code block.
+fn member_block(
+  member: &temp_trait::Item,
+  index: &HashMap<Id, temp_trait::Item>,
+  link_resolver: &links::LinkResolver,
+) -> String {
+  let attributes =
+    ItemAttributes::from_attrs(&attrs::parse_attrs(&member.attrs));
+  let name = member.name.as_deref().unwrap_or("_");
+  let mut block =
+    match (&member.inner, signature::item_signature(member, index)) {
+      (ItemEnum::StructField(type_), _) => {
+        format!("`{name}: {}`", signature::type_to_string(type_))
+      }
+      (ItemEnum::Variant(_), Some(signature)) => format!("`{signature}`"),
+      (_, Some(signature)) => {
+        format!("```rust\n{}{signature}\n```", attributes.signature_prefix())
+      }
+      (_, None) => format!("`{name}`"),
+    };
+  if let Some(deprecation) = &member.deprecation {
+    block.push_str(&format!(
+      "\n\n> {}",
+      render::deprecation_notice(deprecation)
+    ));
+  }
+  if let Some(docs) = member.docs.as_deref().filter(|docs| !docs.is_empty()) {
+    block.push_str("\n\n");
+    block
+      .push_str(&markdown::resolve_links(docs, &link_resolver.links(member)));
+  }
+  block
+}
+
+/// Render names as a comma-separated list of code spans, e.g. `` `Clone`,
+/// `Debug` ``.
+fn code_list(names: &[String]) -> String {
+  names
+    .iter()
+    .map(|name| format!("`{name}`"))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Turn a markdown block into a list item, indenting its continuation lines.
+fn list_item(block: &str) -> String {
+  block
+    .lines()
+    .enumerate()
+    .map(|(i, line)| match (i, line.is_empty()) {
+      (0, _) => format!("- {line}"),
+      (_, true) => String::new(),
+      (_, false) => format!("  {line}"),
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
   #[cfg(feature = "rustdoc")]
   use std::path::PathBuf;
 
+  /// Load the rustdoc JSON fixture of the `demo` crate in `tests/fixtures`.
+  pub(crate) fn fixture_crate() -> rustdoc_types::Crate {
+    serde_json::from_str(include_str!("../tests/fixtures/demo.json")).unwrap()
+  }
+
+  /// Process the fixture crate with `options`.
+  pub(crate) fn process(options: &ProcessOptions) -> CrateDocs {
+    CrateDocs::process_docs("demo", fixture_crate(), None, options).unwrap()
+  }
+
+  /// The session titled `title` and its full session.
+  pub(crate) fn session<'a>(
+    docs: &'a CrateDocs,
+    title: &str,
+  ) -> (&'a SessionItem, &'a FullSessionItem) {
+    let position = docs
+      .sessions
+      .iter()
+      .position(|session| session.title == title)
+      .unwrap_or_else(|| panic!("no session titled `{title}`"));
+    (&docs.sessions[position], &docs.full_sessions[position])
+  }
+
+  /// The titles of the sessions, in order.
+  pub(crate) fn titles(docs: &CrateDocs) -> Vec<&str> {
+    docs
+      .sessions
+      .iter()
+      .map(|session| session.title.as_str())
+      .collect()
+  }
+
+  #[test]
+  fn test_process_docs_descriptions() {
+    let docs = process(&ProcessOptions::default());
+
+    assert_eq!(docs.version, "0.3.1");
+    assert_eq!(
+      docs.sessions[0].description,
+      "A small parsing library used as a rustdoc JSON fixture."
+    );
+    let (parser, _) = session(&docs, "demo::parser");
+    assert_eq!(parser.description, "The tokenizer and its `Token` type.");
+  }
+
+  #[test]
+  fn test_process_docs_qualified_titles() {
+    let docs = process(&ProcessOptions::default());
+    let titles = titles(&docs);
+
+    assert!(titles.contains(&"demo::error::Error"));
+    assert!(titles.contains(&"demo::parser::Error"));
+    assert!(titles.contains(&"demo::parser::Token"));
+    assert!(!titles.contains(&"Error"));
+  }
+
+  #[test]
+  fn test_process_docs_item_links() {
+    let docs = process(&ProcessOptions::default());
+    let (parser, _) = session(&docs, "demo::parser::Parser");
+
+    assert_eq!(
+      parser.link,
+      "https://docs.rs/demo/0.3.1/demo/parser/struct.Parser.html"
+    );
+    assert!(parser
+      .source_link
+      .as_deref()
+      .unwrap()
+      .starts_with("https://docs.rs/crate/demo/0.3.1/source/src/parser.rs#"));
+  }
+
+  #[test]
+  fn test_process_docs_sections() {
+    let docs = process(
+      &ProcessOptions::builder()
+        .deprecated_items(DeprecatedItems::Optional)
+        .build(),
+    );
+    let sections = docs.sections();
+
+    assert_eq!(sections[0].title, "demo: Modules");
+    assert_eq!(sections[0].items[0].title, "demo");
+    assert!(sections
+      .iter()
+      .any(|section| section.title == "demo::parser: Traits"));
+    let optional = sections.last().unwrap();
+    assert!(optional.optional);
+    assert!(optional
+      .items
+      .iter()
+      .any(|item| item.title == "demo::old_parse"));
+  }
+
+  #[test]
+  fn test_process_docs_is_deterministic() {
+    let first = process(&ProcessOptions::default());
+
+    for _ in 0..5 {
+      let again = process(&ProcessOptions::default());
+      assert_eq!(again.to_llms_txt(), first.to_llms_txt());
+      assert_eq!(again.to_llms_full_txt(), first.to_llms_full_txt());
+    }
+
+    let titles = titles(&first);
+    assert_eq!(titles[0], "demo");
+    assert_eq!(titles.iter().filter(|title| **title == "demo").count(), 1);
+    assert_eq!(&titles[1..3], &["demo::error", "demo::parser"]);
+    assert!(first
+      .sessions
+      .windows(2)
+      .all(|pair| { sections::session_order(&pair[0], &pair[1]).is_le() }));
+
+    // The crate-level docs are kept whole in `llms-full.txt`, examples
+    // included
+    assert_eq!(first.full_sessions.len(), first.sessions.len());
+    let (_, root) = session(&first, "demo");
+    assert!(root.content.starts_with("A small parsing library"));
+    assert!(root
+      .content
+      .contains("let parser = demo::Parser::new(\"a b\");"));
+    assert!(first
+      .to_llms_full_txt()
+      .contains("let parser = demo::Parser::new(\"a b\");"));
+  }
+
+  #[test]
+  fn test_process_docs_without_spans() {
+    let clean = process(&ProcessOptions::default());
+    assert!(clean.warnings.is_empty());
+
+    let mut krate = fixture_crate();
+    let mut count_id = None;
+    for item in krate.index.values_mut() {
+      match item.name.as_deref() {
+        Some("Parser") => item.span = None,
+        Some("to_json") => {
+          item.span = None;
+          krate.paths.remove(&item.id);
+        }
+        Some("count") => {
+          item.name = None;
+          item.span = None;
+          krate.paths.remove(&item.id);
+          count_id = Some(item.id.0);
+        }
+        _ => {}
+      }
+    }
+    let count_id = count_id.unwrap();
+
+    let docs =
+      CrateDocs::process_docs("demo", krate, None, &ProcessOptions::default())
+        .unwrap();
+
+    let (parser, _) = session(&docs, "demo::parser::Parser");
+    assert_eq!(
+      parser.link,
+      "https://docs.rs/demo/0.3.1/demo/parser/struct.Parser.html"
+    );
+    assert!(parser.source_link.is_none());
+
+    let (to_json, _) = session(&docs, "to_json");
+    assert_eq!(to_json.link, "https://docs.rs/demo/0.3.1");
+
+    let kinds = |title: &str| {
+      docs
+        .warnings
+        .iter()
+        .filter(|warning| warning.item == title)
+        .map(|warning| warning.kind)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      kinds("demo::parser::Parser"),
+      vec![WarningKind::MissingSpan]
+    );
+    assert_eq!(
+      kinds("to_json"),
+      vec![WarningKind::MissingSpan, WarningKind::MissingLink]
+    );
+    assert_eq!(
+      kinds(&format!("item {count_id}")),
+      vec![
+        WarningKind::MissingTitle,
+        WarningKind::MissingSpan,
+        WarningKind::MissingLink
+      ]
+    );
+  }
+
+  #[test]
+  fn test_process_docs_requires_a_version() {
+    let mut krate = fixture_crate();
+    krate.crate_version = None;
+    let options = ProcessOptions::default();
+    let Err(Error::Config(msg)) =
+      CrateDocs::process_docs("demo", krate.clone(), None, &options)
+    else {
+      panic!("a document without a version should be rejected");
+    };
+    assert_eq!(msg, "the documentation of demo does not record its version");
+
+    let docs =
+      CrateDocs::process_docs("demo", krate, Some("0.3.0".into()), &options)
+        .unwrap();
+    assert_eq!(docs.version, "0.3.0");
+    assert!(docs.sessions[0].link.contains("/0.3.0"));
+  }
+
+  #[test]
+  fn test_process_docs_warnings_follow_max_items() {
+    let mut krate = fixture_crate();
+    for item in krate.index.values_mut() {
+      item.span = None;
+    }
+    let options = ProcessOptions::builder().max_items(2).build();
+    let docs = CrateDocs::process_docs("demo", krate, None, &options).unwrap();
+
+    // Only the items kept are reported
+    let titles = titles(&docs);
+    assert_eq!(titles.len(), 3);
+    assert_eq!(docs.warnings.len(), 2);
+    assert!(docs
+      .warnings
+      .iter()
+      .all(|warning| warning.kind == WarningKind::MissingSpan
+        && titles[1..].contains(&warning.item.as_str())));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_process_docs_non_utf8_filename() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut krate = fixture_crate();
+    let item = krate
+      .index
+      .values_mut()
+      .find(|item| item.name.as_deref() == Some("parse_async"))
+      .unwrap();
+    item.span.as_mut().unwrap().filename =
+      std::path::PathBuf::from(OsStr::from_bytes(b"src/lib\xff.rs"));
+
+    let docs =
+      CrateDocs::process_docs("demo", krate, None, &ProcessOptions::default())
+        .unwrap();
+
+    assert_eq!(docs.warnings.len(), 1);
+    assert_eq!(docs.warnings[0].kind, WarningKind::NonUtf8Path);
+    assert_eq!(docs.warnings[0].item, "demo::parse_async");
+    let (parse_async, _) = session(&docs, "demo::parse_async");
+    assert!(parse_async
+      .source_link
+      .as_deref()
+      .unwrap()
+      .contains("/source/src/lib\u{FFFD}.rs#"));
+  }
+
+  #[test]
+  fn test_process_docs_reexports() {
+    let docs = process(&ProcessOptions::default());
+
+    let titles = titles(&docs);
+    assert!(titles.contains(&"demo::Reexported"));
+    assert!(!titles.iter().any(|title| title.contains("internal")));
+    assert_eq!(
+      titles
+        .iter()
+        .filter(|title| title.ends_with("::Parser"))
+        .collect::<Vec<_>>(),
+      vec![&"demo::parser::Parser"]
+    );
+
+    let (reexported, _) = session(&docs, "demo::Reexported");
+    assert_eq!(
+      reexported.link,
+      "https://docs.rs/demo/0.3.1/demo/struct.Reexported.html"
+    );
+    assert_eq!(reexported.module, "demo");
+  }
+
+  #[test]
+  fn test_process_docs_undocumented() {
+    let docs = process(&ProcessOptions::default());
+    assert!(!docs.sessions.iter().any(|session| session.undocumented));
+    assert!(!titles(&docs).contains(&"demo::undocumented"));
+
+    let docs =
+      process(&ProcessOptions::builder().include_undocumented(true).build());
+    let (undocumented, full_session) = session(&docs, "demo::undocumented");
+    assert!(undocumented.undocumented);
+    assert_eq!(undocumented.description, "");
+    assert!(full_session.undocumented);
+    assert_eq!(
+      full_session.content,
+      "```rust\npub fn undocumented(value: u8) -> u8\n```"
+    );
+    assert!(docs.to_llms_txt().contains(
+      "- [demo::undocumented](https://docs.rs/demo/0.3.1/demo/fn.undocumented.html): Undocumented.\n"
+    ));
+  }
+
+  #[test]
+  fn test_process_docs_filters() {
+    let defaults = process(&ProcessOptions::default());
+    let defaults = titles(&defaults);
+    assert!(defaults.contains(&"demo::parse"));
+    assert!(defaults.contains(&"demo::old_parse"));
+
+    let docs = process(
+      &ProcessOptions::builder()
+        .exclude_kind(ItemKind::Function)
+        .build(),
+    );
+    let filtered = titles(&docs);
+    assert!(!filtered.contains(&"demo::parse"));
+    assert!(!filtered.contains(&"demo::count"));
+    assert!(filtered.contains(&"demo::parser::Parser"));
+
+    let docs = process(
+      &ProcessOptions::builder()
+        .include_path("demo::parser::**")
+        .exclude_path("demo::parser::Parser::position")
+        .build(),
+    );
+    let filtered = titles(&docs);
+    assert_eq!(filtered[0], "demo");
+    assert!(filtered[1..]
+      .iter()
+      .all(|title| title.starts_with("demo::parser::")));
+    assert!(filtered.contains(&"demo::parser::Visitor"));
+    assert!(!filtered.contains(&"demo::parse"));
+    let (_, parser) = session(&docs, "demo::parser::Parser");
+    assert!(parser.content.contains("pub fn new("));
+    assert!(!parser.content.contains("pub fn position("));
+
+    // Items without a path are titled by their name and cannot match
+    let mut krate = fixture_crate();
+    krate
+      .paths
+      .retain(|_, summary| summary.path != ["demo", "parse"]);
+    let options = ProcessOptions::builder()
+      .include_path("demo::parser::**")
+      .build();
+    let docs = CrateDocs::process_docs("demo", krate, None, &options).unwrap();
+    assert!(!titles(&docs).contains(&"parse"));
+
+    let docs =
+      process(&ProcessOptions::builder().include_deprecated(false).build());
+    let filtered = titles(&docs);
+    assert!(!filtered.contains(&"demo::old_parse"));
+    assert_eq!(filtered.len(), defaults.len() - 1);
+
+    let docs = process(&ProcessOptions::builder().max_items(3).build());
+    assert_eq!(titles(&docs), defaults[..4]);
+  }
+
+  #[test]
+  fn test_process_docs_hidden_and_private() {
+    let docs = |options: ProcessOptions| {
+      let mut krate = fixture_crate();
+      for item in krate.index.values_mut() {
+        match item.name.as_deref() {
+          Some("parse_async") => item
+            .attrs
+            .push(rustdoc_types::Attribute::Other("#[doc(hidden)]".into())),
+          Some("position") => item.visibility = Visibility::Crate,
+          _ => {}
+        }
+      }
+      CrateDocs::process_docs("demo", krate, None, &options).unwrap()
+    };
+
+    let defaults = docs(ProcessOptions::default());
+    assert!(!titles(&defaults).contains(&"demo::parse_async"));
+    let (_, parser) = session(&defaults, "demo::parser::Parser");
+    assert!(!parser.content.contains("fn position("));
+
+    let all = docs(
+      ProcessOptions::builder()
+        .include_hidden(true)
+        .includes_private(true)
+        .build(),
+    );
+    assert!(titles(&all).contains(&"demo::parse_async"));
+    let (_, parser) = session(&all, "demo::parser::Parser");
+    assert!(parser.content.contains("fn position("));
+  }
+
+  #[test]
+  fn test_process_docs_signatures() {
+    let docs = process(&ProcessOptions::default());
+    let (_, parse) = session(&docs, "demo::parse");
+
+    assert!(parse.content.starts_with(
+      "```rust\npub fn parse(input: &str) -> Result<Vec<Token>>\n```\n\nParse `input`"
+    ));
+  }
+
   #[tokio::test]
   async fn test_from_online() {
     let lib_name = "clap";
     let version = "4.5.42".to_string();
-    let result = CrateDocs::from_online(lib_name, Some(version.clone())).await;
+    let result = CrateDocs::from_online(
+      lib_name,
+      Some(version.clone()),
+      &OnlineDocs::default(),
+      &ProcessOptions::default(),
+    )
+    .await;
 
     match result {
       Ok(docs) => {
@@ -516,9 +1566,11 @@ mod tests {
         println!("Successfully fetched docs for {}", lib_name);
       }
       Err(e) => {
-        // If it fails due to version compatibility, that's expected for some crates
+        // docs.rs may serve a format_version newer than the supported range
         match e {
-          crate::error::Error::Config(msg) if msg.contains("incompatible") => {
+          crate::error::Error::Config(msg)
+            if msg.starts_with("unsupported format_version") =>
+          {
             println!(
               "Expected version compatibility issue with {}: {}",
               lib_name, msg
@@ -541,6 +1593,7 @@ mod tests {
     let docs = CrateDocs::from_local(
       current_dir.join("Cargo.toml"),
       Some("stable".to_string()),
+      &ProcessOptions::default(),
     )
     .unwrap();
     assert_eq!(docs.lib_name, lib_name);
@@ -553,8 +1606,11 @@ mod tests {
     let current_dir = std::env::current_dir().unwrap();
     let manifest_path = current_dir.join("Cargo.toml");
 
-    let result =
-      CrateDocs::from_local(manifest_path.clone(), Some("stable".to_string()));
+    let result = CrateDocs::from_local(
+      manifest_path.clone(),
+      Some("stable".to_string()),
+      &ProcessOptions::default(),
+    );
     assert!(result.is_ok());
 
     let docs = result.unwrap();
@@ -569,7 +1625,11 @@ mod tests {
     let current_dir = std::env::current_dir().unwrap();
     let manifest_path = current_dir.join("Cargo.toml");
 
-    let result = CrateDocs::from_local(manifest_path.clone(), None);
+    let result = CrateDocs::from_local(
+      manifest_path.clone(),
+      None,
+      &ProcessOptions::default(),
+    );
     assert!(result.is_ok());
 
     let docs = result.unwrap();
@@ -582,7 +1642,8 @@ mod tests {
   #[test]
   fn test_from_local_invalid_path() {
     let invalid_path = PathBuf::from("/invalid/path/Cargo.toml");
-    let result = CrateDocs::from_local(invalid_path, None);
+    let result =
+      CrateDocs::from_local(invalid_path, None, &ProcessOptions::default());
     assert!(result.is_err());
   }
 
@@ -598,6 +1659,7 @@ mod tests {
       false,
       Some(vec!["rustdoc".to_string()]),
       Some("stable".to_string()),
+      &ProcessOptions::default(),
     );
     assert!(result.is_ok());
 
@@ -612,13 +1674,19 @@ mod tests {
       true,
       None,
       None,
+      &ProcessOptions::default(),
     );
     assert!(result.is_ok());
 
     // Test with invalid path
     let invalid_path = PathBuf::from("/invalid/path/Cargo.toml");
-    let result =
-      CrateDocs::from_local_with_features(invalid_path, false, None, None);
+    let result = CrateDocs::from_local_with_features(
+      invalid_path,
+      false,
+      None,
+      None,
+      &ProcessOptions::default(),
+    );
     assert!(result.is_err());
   }
 }
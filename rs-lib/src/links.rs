@@ -0,0 +1,171 @@
+//! # Intra-doc Link Resolution
+//!
+//! Doc comments refer to other items through intra-doc links such as
+//! ``[`Parser`]`` or `[parse](crate::Parser::parse)`. Rustdoc resolves them to
+//! item ids, recorded in `Item::links`, but leaves the markdown untouched, so
+//! outside of rustdoc they render as broken references. This module computes
+//! the absolute URL of each link target: docs.rs pages for items of the crate,
+//! pages anchored at the member for methods and fields, and the documentation
+//! of the dependency for external items.
+
+use std::collections::HashMap;
+
+use rustdoc_types::{ExternalCrate, Id, ItemEnum, ItemSummary};
+
+use crate::temp_trait::Item;
+use crate::{sections, urls};
+
+/// Resolves the targets of intra-doc links to URLs.
+pub struct LinkResolver<'a> {
+  lib_name: &'a str,
+  version: &'a str,
+  index: &'a HashMap<Id, Item>,
+  paths: &'a HashMap<Id, ItemSummary>,
+  member_parents: &'a HashMap<Id, Id>,
+  external_crates: &'a HashMap<u32, ExternalCrate>,
+}
+
+impl<'a> LinkResolver<'a> {
+  /// Creates a resolver for the items of a crate.
+  ///
+  /// # Arguments
+  ///
+  /// * `lib_name` - The name of the crate as published on crates.io
+  /// * `version` - The version of the crate
+  /// * `index` - The crate index, from `CommonCrates::index`
+  /// * `paths` - The path summaries, with public paths already applied
+  /// * `member_parents` - The parent of each member, from
+  ///   `reexports::member_parents`
+  /// * `external_crates` - The dependencies, from
+  ///   `CommonCrates::external_crates`
+  pub fn new(
+    lib_name: &'a str,
+    version: &'a str,
+    index: &'a HashMap<Id, Item>,
+    paths: &'a HashMap<Id, ItemSummary>,
+    member_parents: &'a HashMap<Id, Id>,
+    external_crates: &'a HashMap<u32, ExternalCrate>,
+  ) -> Self {
+    Self {
+      lib_name,
+      version,
+      index,
+      paths,
+      member_parents,
+      external_crates,
+    }
+  }
+
+  /// Resolve the intra-doc links of an item.
+  ///
+  /// # Returns
+  ///
+  /// * `HashMap<String, String>` - The URL of each link destination found in
+  ///   `Item::links`; destinations that cannot be resolved are left out
+  pub fn links(&self, item: &Item) -> HashMap<String, String> {
+    item
+      .links
+      .iter()
+      .filter_map(|(destination, id)| {
+        Some((destination.clone(), self.url(id)?))
+      })
+      .collect()
+  }
+
+  /// Compute the URL of an item, local or external.
+  pub fn url(&self, id: &Id) -> Option<String> {
+    if let Some(summary) = self.paths.get(id) {
+      return self.summary_url(summary);
+    }
+
+    // Members are rendered on the page of their parent
+    let parent = self.member_parents.get(id)?;
+    let member = self.index.get(id)?;
+    let required = matches!(
+      (&member.inner, self.index.get(parent).map(|parent| &parent.inner)),
+      (ItemEnum::Function(function), Some(ItemEnum::Trait(_)))
+        if !function.has_body
+    );
+    let anchor = urls::member_anchor(
+      sections::item_kind(&member.inner),
+      member.name.as_deref()?,
+      required,
+    )?;
+    let page = self.summary_url(self.paths.get(parent)?)?;
+    Some(format!("{page}#{anchor}"))
+  }
+
+  fn summary_url(&self, summary: &ItemSummary) -> Option<String> {
+    if summary.crate_id == 0 {
+      return urls::item_url(self.lib_name, self.version, summary);
+    }
+    urls::external_item_url(
+      self.external_crates.get(&summary.crate_id)?,
+      summary,
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::reexports;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::{fixture_crate, process, session};
+
+  #[test]
+  fn test_resolve_fixture_links() {
+    let krate = fixture_crate();
+    let (index, paths) = (krate.index(), krate.paths());
+    let parents = reexports::member_parents(&index, &paths);
+    let external_crates = krate.external_crates();
+    let resolver = LinkResolver::new(
+      "demo",
+      "0.3.1",
+      &index,
+      &paths,
+      &parents,
+      &external_crates,
+    );
+    let links_of = |name: &str| {
+      let item = index
+        .values()
+        .find(|item| {
+          item.name.as_deref() == Some(name) && !item.links.is_empty()
+        })
+        .unwrap();
+      resolver.links(item)
+    };
+
+    let parser = links_of("Parser");
+    assert_eq!(
+      parser["`Parser::parse`"],
+      "https://docs.rs/demo/0.3.1/demo/parser/struct.Parser.html#method.parse"
+    );
+    assert_eq!(
+      parser["`Visitor`"],
+      "https://docs.rs/demo/0.3.1/demo/parser/trait.Visitor.html"
+    );
+    assert_eq!(
+      links_of("demo")["`Vec`"],
+      "https://doc.rust-lang.org/1.90.0/alloc/vec/struct.Vec.html"
+    );
+  }
+
+  #[test]
+  fn test_process_docs_intra_doc_links() {
+    let docs = process(&ProcessOptions::default());
+
+    let (_, parser) = session(&docs, "demo::parser::Parser");
+    assert!(parser.content.contains(
+      "See [`Parser::parse`](https://docs.rs/demo/0.3.1/demo/parser/struct.Parser.html#method.parse) \
+       and the [`Visitor`](https://docs.rs/demo/0.3.1/demo/parser/trait.Visitor.html) trait."
+    ));
+    // Re-exported items link to their public path
+    let (_, parse) = session(&docs, "demo::parse");
+    assert!(parse.content.contains(
+      "[`Parser`](https://docs.rs/demo/0.3.1/demo/parser/struct.Parser.html)"
+    ));
+  }
+}
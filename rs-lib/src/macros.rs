@@ -0,0 +1,237 @@
+//! # Macro Listing
+//!
+//! A `macro_rules!` item carries its source in rustdoc JSON, and a procedural
+//! macro its kind and the helper attributes of a derive. This module extracts
+//! the matchers of a declarative macro, so that its rules can be shown without
+//! their expansion, and describes how a macro is invoked.
+
+use rustdoc_types::{ItemEnum, MacroKind};
+
+use crate::temp_trait::Item;
+
+/// Extract the matchers of the rules of a `macro_rules!` definition.
+///
+/// # Arguments
+///
+/// * `source` - The macro source, from `ItemEnum::Macro`
+///
+/// # Returns
+///
+/// * `Option<(&str, Vec<&str>)>` - The macro name and its matchers with their
+///   delimiters, e.g. `($x:expr)`, in declaration order; `None` when the
+///   source is not a well-formed `macro_rules!` definition
+pub fn macro_rules_matchers(source: &str) -> Option<(&str, Vec<&str>)> {
+  let rest = source.trim().strip_prefix("macro_rules!")?;
+  let open = rest.find(['{', '(', '['])?;
+  let name = rest[..open].trim();
+  let len = group_len(&rest[open..])?;
+  let mut body = skip_trivia(&rest[open + 1..open + len - 1]);
+
+  let mut matchers = Vec::new();
+  while !body.is_empty() {
+    let len = group_len(body)?;
+    matchers.push(&body[..len]);
+    body = skip_trivia(&body[len..]).strip_prefix("=>")?;
+    body = skip_trivia(body);
+    let len = group_len(body)?;
+    body = skip_trivia(&body[len..]);
+    body = skip_trivia(body.strip_prefix(';').unwrap_or(body));
+  }
+  Some((name, matchers))
+}
+
+/// Describe the kind of a macro and how it is invoked, e.g.
+/// `` Attribute macro, applied as `#[main]` ``.
+///
+/// # Returns
+///
+/// * `Option<String>` - The description; `None` for items that are not macros
+pub fn macro_kind(item: &Item) -> Option<String> {
+  let name = item.name.as_deref().unwrap_or("_");
+  let kind = match &item.inner {
+    ItemEnum::Macro(source) => match macro_rules_matchers(source) {
+      Some((_, matchers)) if matchers.len() == 1 => {
+        format!("Declarative macro (`macro_rules!`) with 1 rule, invoked as `{name}!`")
+      }
+      Some((_, matchers)) => format!(
+        "Declarative macro (`macro_rules!`) with {} rules, invoked as `{name}!`",
+        matchers.len()
+      ),
+      None => format!("Declarative macro, invoked as `{name}!`"),
+    },
+    ItemEnum::ProcMacro(proc_macro) => match proc_macro.kind {
+      MacroKind::Bang => {
+        format!("Function-like procedural macro, invoked as `{name}!(...)`")
+      }
+      MacroKind::Attr => format!("Attribute macro, applied as `#[{name}]`"),
+      MacroKind::Derive if proc_macro.helpers.is_empty() => {
+        format!("Derive macro, applied as `#[derive({name})]`")
+      }
+      MacroKind::Derive => format!(
+        "Derive macro, applied as `#[derive({name})]`, with helper \
+         attributes {}",
+        proc_macro
+          .helpers
+          .iter()
+          .map(|helper| format!("`#[{helper}]`"))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    },
+    _ => return None,
+  };
+  Some(kind)
+}
+
+/// Byte length of the delimited group at the start of `source`, including
+/// its delimiters, skipping over string and character literals and comments.
+fn group_len(source: &str) -> Option<usize> {
+  if !source.starts_with(['{', '(', '[']) {
+    return None;
+  }
+  let mut depth = 0usize;
+  let mut chars = source.char_indices().peekable();
+  while let Some((i, c)) = chars.next() {
+    match c {
+      '{' | '(' | '[' => depth += 1,
+      '}' | ')' | ']' => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(i + 1);
+        }
+      }
+      '"' => {
+        while let Some((_, c)) = chars.next() {
+          match c {
+            '\\' => {
+              chars.next();
+            }
+            '"' => break,
+            _ => {}
+          }
+        }
+      }
+      // A character literal, as opposed to a lifetime, closes after one
+      // (possibly escaped) character
+      '\'' => {
+        let rest = &source[i + 1..];
+        let len = match rest.strip_prefix('\\') {
+          Some(escaped) => escaped.find('\'').map(|end| end + 2),
+          None => rest
+            .chars()
+            .next()
+            .filter(|_| rest.chars().nth(1) == Some('\''))
+            .map(char::len_utf8),
+        };
+        if let Some(len) = len {
+          while chars.peek().is_some_and(|&(j, _)| j <= i + len + 1) {
+            chars.next();
+          }
+        }
+      }
+      '/' if source[i..].starts_with("//") => {
+        while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+      }
+      '/' if source[i..].starts_with("/*") => {
+        let end = source[i + 2..].find("*/")? + i + 4;
+        while chars.next_if(|&(j, _)| j < end).is_some() {}
+      }
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Skip the whitespace and comments at the start of `source`.
+fn skip_trivia(mut source: &str) -> &str {
+  loop {
+    source = source.trim_start();
+    if let Some(rest) = source.strip_prefix("//") {
+      source = rest.find('\n').map_or("", |end| &rest[end..]);
+    } else if let Some(rest) = source.strip_prefix("/*") {
+      source = rest.find("*/").map_or("", |end| &rest[end + 2..]);
+    } else {
+      return source;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::{fixture_crate, process, session};
+
+  #[test]
+  fn test_macro_rules_matchers() {
+    let index = fixture_crate().index();
+    let tokens = index
+      .values()
+      .find(|item| matches!(item.inner, ItemEnum::Macro(_)))
+      .unwrap();
+    let ItemEnum::Macro(source) = &tokens.inner else {
+      unreachable!()
+    };
+    assert_eq!(
+      macro_rules_matchers(source),
+      Some(("tokens", vec!["()", "($($word:expr),+ $(,)?)"]))
+    );
+    assert_eq!(
+      macro_kind(tokens).unwrap(),
+      "Declarative macro (`macro_rules!`) with 2 rules, invoked as `tokens!`"
+    );
+
+    // Full sources keep their expansion, which may hold unbalanced
+    // delimiters in literals and comments
+    let source = "macro_rules! pair {\n    // one ( two\n    ($a:expr, ')') => {\n        ($a, \"]\")\n    };\n    [$a:lifetime] => { /* } */ };\n}";
+    assert_eq!(
+      macro_rules_matchers(source),
+      Some(("pair", vec!["($a:expr, ')')", "[$a:lifetime]"]))
+    );
+    assert_eq!(macro_rules_matchers("pub macro pair($a:expr) {}"), None);
+  }
+
+  #[test]
+  fn test_proc_macro_kind() {
+    let mut item = fixture_crate()
+      .index()
+      .into_values()
+      .find(|item| matches!(item.inner, ItemEnum::Macro(_)))
+      .unwrap();
+    let mut proc_macro = |kind, helpers: &[&str]| {
+      item.inner = ItemEnum::ProcMacro(rustdoc_types::ProcMacro {
+        kind,
+        helpers: helpers.iter().map(|helper| helper.to_string()).collect(),
+      });
+      macro_kind(&item).unwrap()
+    };
+
+    assert_eq!(
+      proc_macro(MacroKind::Bang, &[]),
+      "Function-like procedural macro, invoked as `tokens!(...)`"
+    );
+    assert_eq!(
+      proc_macro(MacroKind::Attr, &[]),
+      "Attribute macro, applied as `#[tokens]`"
+    );
+    assert_eq!(
+      proc_macro(MacroKind::Derive, &["token", "skip"]),
+      "Derive macro, applied as `#[derive(tokens)]`, with helper attributes \
+       `#[token]`, `#[skip]`"
+    );
+  }
+
+  #[test]
+  fn test_process_docs_macros() {
+    let docs = process(&ProcessOptions::default());
+    let content = &session(&docs, "demo::tokens").1.content;
+
+    assert!(content
+      .starts_with("```rust\nmacro_rules! tokens {\n    () => { ... };\n"));
+    assert!(content.ends_with(
+      "# Macro Kind\n\n\
+       Declarative macro (`macro_rules!`) with 2 rules, invoked as `tokens!`"
+    ));
+  }
+}
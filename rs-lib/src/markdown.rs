@@ -0,0 +1,356 @@
+//! # Markdown Helpers
+//!
+//! Small, dependency-free helpers for working with the markdown found in
+//! rustdoc doc comments. They are deliberately lenient: doc comments are not
+//! required to be valid CommonMark, so anything unexpected is passed through.
+
+use std::collections::HashMap;
+
+/// Maximum length, in characters, of a generated summary
+const MAX_SUMMARY_LEN: usize = 200;
+
+/// Build a short plain-text summary from an item's markdown documentation.
+///
+/// The summary is the first paragraph of prose, skipping headings and code
+/// fences. Markdown links are replaced by their text, whitespace is collapsed
+/// and the result is shortened to its first sentence, or truncated at a word
+/// boundary, if it exceeds the maximum summary length.
+///
+/// # Arguments
+///
+/// * `docs` - Markdown documentation of an item
+///
+/// # Returns
+///
+/// * `String` - The summary, or an empty string if no prose was found
+pub fn summarize(docs: &str) -> String {
+  let paragraph = first_paragraph(docs);
+  let text = strip_links(&paragraph)
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ");
+
+  shorten(&text)
+}
+
+/// Return the first paragraph of prose, ignoring headings and code blocks.
+fn first_paragraph(docs: &str) -> String {
+  let mut lines = Vec::new();
+  let mut in_fence = false;
+
+  for line in docs.lines() {
+    let trimmed = line.trim();
+
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+      in_fence = !in_fence;
+      if !lines.is_empty() {
+        break;
+      }
+      continue;
+    }
+    if in_fence {
+      continue;
+    }
+
+    if trimmed.is_empty() {
+      if !lines.is_empty() {
+        break;
+      }
+      continue;
+    }
+    if lines.is_empty() && trimmed.starts_with('#') {
+      continue;
+    }
+
+    lines.push(trimmed);
+  }
+
+  lines.join(" ")
+}
+
+/// Replace markdown links with their text.
+///
+/// Handles inline links (`[text](url)`), reference links (`[text][ref]`) and
+/// shortcut links such as rustdoc intra-doc links (``[`Type`]``).
+fn strip_links(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut rest = text;
+
+  while let Some(start) = rest.find(['[', '`']) {
+    out.push_str(&rest[..start]);
+    rest = &rest[start..];
+
+    // Copy code spans through unchanged, brackets included
+    if rest.starts_with('`') {
+      let end = code_span_len(rest);
+      out.push_str(&rest[..end]);
+      rest = &rest[end..];
+      continue;
+    }
+
+    let Some(end) = closing_bracket(rest, '[', ']') else {
+      out.push_str(rest);
+      return out;
+    };
+
+    out.push_str(&rest[1..end]);
+    rest = &rest[end + 1..];
+
+    // Skip the destination of inline and reference links
+    let destination = match rest.chars().next() {
+      Some('(') => closing_bracket(rest, '(', ')'),
+      Some('[') => closing_bracket(rest, '[', ']'),
+      _ => None,
+    };
+    if let Some(close) = destination {
+      rest = &rest[close + 1..];
+    }
+  }
+
+  out.push_str(rest);
+  out
+}
+
+/// Rewrite intra-doc links into inline links to absolute URLs.
+///
+/// `links` maps link destinations, as found in `Item::links` (e.g.
+/// ``"`Parser`"`` for ``[`Parser`]`` or `crate::Parser` for
+/// `[parser](crate::Parser)`), to their URL. Shortcut (``[`Parser`]``),
+/// collapsed (`[Parser][]`), reference (`[the parser][crate::Parser]`) and
+/// inline links are rewritten, as are reference definitions
+/// (`[parser]: crate::Parser`). Links without a known destination, and
+/// anything inside code blocks or code spans, are left untouched.
+///
+/// # Arguments
+///
+/// * `docs` - Markdown documentation of an item
+/// * `links` - Resolved URL of each intra-doc link destination
+///
+/// # Returns
+///
+/// * `String` - The documentation with intra-doc links resolved
+pub fn resolve_links(docs: &str, links: &HashMap<String, String>) -> String {
+  if links.is_empty() {
+    return docs.to_string();
+  }
+
+  let mut out = Vec::new();
+  let mut in_fence = false;
+  for line in docs.split('\n') {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+      in_fence = !in_fence;
+      out.push(line.to_string());
+    } else if in_fence {
+      out.push(line.to_string());
+    } else if let Some(definition) = resolve_definition(line, links) {
+      out.push(definition);
+    } else {
+      out.push(resolve_inline_links(line, links));
+    }
+  }
+  out.join("\n")
+}
+
+/// Rewrite a reference definition such as `[parser]: crate::Parser`.
+fn resolve_definition(
+  line: &str,
+  links: &HashMap<String, String>,
+) -> Option<String> {
+  let trimmed = line.trim_start();
+  let indent = &line[..line.len() - trimmed.len()];
+  let label_end = trimmed.strip_prefix('[')?.find("]:")? + 1;
+  let destination = trimmed[label_end + 2..].trim();
+  let url =
+    links.get(destination.trim_start_matches('<').trim_end_matches('>'))?;
+  Some(format!("{indent}{}: {url}", &trimmed[..=label_end]))
+}
+
+/// Rewrite the links of a single line outside of code blocks.
+fn resolve_inline_links(line: &str, links: &HashMap<String, String>) -> String {
+  let mut out = String::with_capacity(line.len());
+  let mut rest = line;
+
+  while let Some(start) = rest.find(['[', '`']) {
+    out.push_str(&rest[..start]);
+    rest = &rest[start..];
+
+    // Copy code spans through unchanged
+    if rest.starts_with('`') {
+      let end = code_span_len(rest);
+      out.push_str(&rest[..end]);
+      rest = &rest[end..];
+      continue;
+    }
+
+    let Some(text_end) = closing_bracket(rest, '[', ']') else {
+      out.push_str(rest);
+      return out;
+    };
+    let text = &rest[1..text_end];
+    let after = &rest[text_end + 1..];
+
+    // Inline `[text](destination)` and reference `[text][destination]` links
+    let (destination, consumed) = match after.chars().next() {
+      Some(open @ ('(' | '[')) => {
+        let close = if open == '(' { ')' } else { ']' };
+        match closing_bracket(after, open, close) {
+          Some(end) => (Some(&after[1..end]), end + 1),
+          None => (None, 0),
+        }
+      }
+      _ => (None, 0),
+    };
+    let key = match destination {
+      Some("") | None => text,
+      Some(destination) => destination,
+    };
+
+    match links.get(key) {
+      Some(url) => {
+        out.push_str(&format!("[{text}]({url})"));
+        rest = &after[consumed..];
+      }
+      None => {
+        // Keep the text as is, but look for links nested in it
+        out.push('[');
+        rest = &rest[1..];
+      }
+    }
+  }
+
+  out.push_str(rest);
+  out
+}
+
+/// Byte length of the code span `text` starts with, up to the closing run of
+/// backticks, or of the whole text if the span is not closed.
+fn code_span_len(text: &str) -> usize {
+  let ticks = text.len() - text.trim_start_matches('`').len();
+  let fence = &text[..ticks];
+  text[ticks..]
+    .find(fence)
+    .map_or(text.len(), |end| ticks + end + ticks)
+}
+
+/// Find the byte offset of the bracket closing the one `text` starts with.
+fn closing_bracket(text: &str, open: char, close: char) -> Option<usize> {
+  let mut depth = 0usize;
+  let mut in_code = false;
+  for (index, char) in text.char_indices() {
+    match char {
+      '`' => in_code = !in_code,
+      _ if in_code => {}
+      _ if char == open => depth += 1,
+      _ if char == close => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(index);
+        }
+      }
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Shorten a summary to its first sentence or to the maximum length.
+fn shorten(text: &str) -> String {
+  if text.chars().count() <= MAX_SUMMARY_LEN {
+    return text.to_string();
+  }
+
+  if let Some(end) = text.find(". ") {
+    if text[..end].chars().count() < MAX_SUMMARY_LEN {
+      return text[..=end].to_string();
+    }
+  }
+
+  let mut out = String::new();
+  for word in text.split(' ') {
+    if out.chars().count() + word.chars().count() + 1 > MAX_SUMMARY_LEN {
+      break;
+    }
+    if !out.is_empty() {
+      out.push(' ');
+    }
+    out.push_str(word);
+  }
+  out.push_str("...");
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_summarize_first_paragraph() {
+    let docs = "# Heading\n\nParses a [`Config`] from\nthe [input](https://example.com).\n\nMore details.";
+    assert_eq!(summarize(docs), "Parses a `Config` from the input.");
+  }
+
+  #[test]
+  fn test_summarize_skips_code_fences() {
+    let docs = "```rust\nlet x = 1;\n```\n\nCreates a value.";
+    assert_eq!(summarize(docs), "Creates a value.");
+    assert_eq!(summarize("```\nonly code\n```"), "");
+  }
+
+  #[test]
+  fn test_summarize_keeps_brackets_in_code_spans() {
+    assert_eq!(
+      summarize("Returns `arr[0]`, like [`first`](Self::first)."),
+      "Returns `arr[0]`, like `first`."
+    );
+    assert_eq!(
+      summarize("Indexes with ``v[i]`` or [`get`][`a[0]`]."),
+      "Indexes with ``v[i]`` or `get`."
+    );
+  }
+
+  #[test]
+  fn test_resolve_links() {
+    let links = HashMap::from([
+      (
+        "`Parser`".to_string(),
+        "https://example.com/Parser".to_string(),
+      ),
+      (
+        "crate::Token".to_string(),
+        "https://example.com/Token".to_string(),
+      ),
+    ]);
+    let docs = "See [`Parser`], [tokens](crate::Token) and [the token][crate::Token].\n\
+                Also [`Parser`][] and [`Missing`] or [web](https://rust-lang.org).\n\
+                \n\
+                ```\n\
+                let x = [`Parser`];\n\
+                ```\n\
+                ``[`Parser`]`` stays.\n\
+                [token]: crate::Token";
+
+    assert_eq!(
+      resolve_links(docs, &links),
+      "See [`Parser`](https://example.com/Parser), [tokens](https://example.com/Token) and [the token](https://example.com/Token).\n\
+       Also [`Parser`](https://example.com/Parser) and [`Missing`] or [web](https://rust-lang.org).\n\
+       \n\
+       ```\n\
+       let x = [`Parser`];\n\
+       ```\n\
+       ``[`Parser`]`` stays.\n\
+       [token]: https://example.com/Token"
+    );
+  }
+
+  #[test]
+  fn test_summarize_caps_length() {
+    let long = "word ".repeat(100);
+    let summary = summarize(&long);
+    assert!(summary.ends_with("..."));
+    assert!(summary.chars().count() <= MAX_SUMMARY_LEN + 3);
+
+    let sentences = format!("Short sentence. {}", long);
+    assert_eq!(summarize(&sentences), "Short sentence.");
+  }
+}
@@ -0,0 +1,332 @@
+//! # Processing Options
+//!
+//! Options controlling which items of a crate end up in the generated
+//! documentation. They are accepted by every `CrateDocs::from_*` constructor.
+//! `ProcessOptions::default()` lists public, documented items of every kind,
+//! with no limit on their number, and leaves out items marked
+//! `#[doc(hidden)]`, as rustdoc does.
+//!
+//! Options are usually built with [`ProcessOptions::builder`]:
+//!
+//! ```
+//! use crates_llms_txt::options::{DeprecatedItems, ItemKind, ProcessOptions};
+//!
+//! let options = ProcessOptions::builder()
+//!   .exclude_kind(ItemKind::Macro)
+//!   .exclude_path("demo::internal::**")
+//!   .deprecated_items(DeprecatedItems::Optional)
+//!   .max_items(500)
+//!   .build();
+//! assert!(!options.allows_kind(ItemKind::Macro));
+//! assert!(!options.allows_path("demo::internal::Helper"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+pub use rustdoc_types::ItemKind;
+
+/// Options controlling how rustdoc JSON is turned into `CrateDocs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessOptions {
+  /// Include public items without doc comments. Their content is their
+  /// rendered signature, including fields and variants, and their sessions
+  /// are marked as undocumented.
+  pub include_undocumented: bool,
+  /// Include `pub(crate)` and `pub(in path)` items, and items of private
+  /// modules. Such items are only present in rustdoc JSON generated with
+  /// `--document-private-items`.
+  pub includes_private: bool,
+  /// How items marked `#[deprecated]` are listed
+  pub deprecated_items: DeprecatedItems,
+  /// Include items marked `#[doc(hidden)]`. Such items are only present in
+  /// rustdoc JSON generated with `--document-hidden-items`.
+  pub include_hidden: bool,
+  /// List auto trait (`Send`, `Sync`, ...) and blanket (`impl<T> From<T> for
+  /// T`, ...) implementations among the traits implemented by a type
+  pub include_blanket_impls: bool,
+  /// Only include items of these kinds; every kind is included when empty
+  pub include_kinds: Vec<ItemKind>,
+  /// Never include items of these kinds
+  pub exclude_kinds: Vec<ItemKind>,
+  /// Only include items whose path matches one of these glob patterns; every
+  /// path is included when empty
+  pub include_paths: Vec<String>,
+  /// Never include items whose path matches one of these glob patterns
+  pub exclude_paths: Vec<String>,
+  /// Maximum number of items listed after the crate root; unlimited if `None`
+  pub max_items: Option<usize>,
+  /// Only include items whose required cargo features are all part of this
+  /// set; items that are not feature-gated are always included. Every item
+  /// is included if `None`
+  pub enabled_features: Option<Vec<String>>,
+}
+
+impl Default for ProcessOptions {
+  fn default() -> Self {
+    Self {
+      include_undocumented: false,
+      includes_private: false,
+      deprecated_items: DeprecatedItems::Include,
+      include_hidden: false,
+      include_blanket_impls: true,
+      include_kinds: Vec::new(),
+      exclude_kinds: Vec::new(),
+      include_paths: Vec::new(),
+      exclude_paths: Vec::new(),
+      max_items: None,
+      enabled_features: None,
+    }
+  }
+}
+
+impl ProcessOptions {
+  /// Creates a builder starting from the default options.
+  pub fn builder() -> ProcessOptionsBuilder {
+    ProcessOptionsBuilder::default()
+  }
+
+  /// Whether items of the given kind are included.
+  pub fn allows_kind(&self, kind: ItemKind) -> bool {
+    (self.include_kinds.is_empty() || self.include_kinds.contains(&kind))
+      && !self.exclude_kinds.contains(&kind)
+  }
+
+  /// Whether an item with the given path is included.
+  ///
+  /// Paths are `::`-separated, e.g. `clap::builder::Arg::new`. In patterns,
+  /// `*` matches any sequence of characters within a path segment and `**`
+  /// matches any sequence of characters, including `::`.
+  pub fn allows_path(&self, path: &str) -> bool {
+    (self.include_paths.is_empty()
+      || self
+        .include_paths
+        .iter()
+        .any(|pattern| glob_match(pattern, path)))
+      && !self
+        .exclude_paths
+        .iter()
+        .any(|pattern| glob_match(pattern, path))
+  }
+
+  /// Whether an item requiring the given cargo features is included.
+  pub fn allows_features(&self, required: &[String]) -> bool {
+    match &self.enabled_features {
+      Some(enabled) => required.iter().all(|feature| enabled.contains(feature)),
+      None => true,
+    }
+  }
+}
+
+/// How items marked `#[deprecated]` are listed.
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecatedItems {
+  /// List deprecated items in their regular section
+  #[default]
+  Include,
+  /// List deprecated items in the trailing "Optional" section
+  Optional,
+  /// Leave deprecated items out
+  Exclude,
+}
+
+impl DeprecatedItems {
+  /// Parse a mode from its snake_case name: `include`, `optional` or
+  /// `exclude`.
+  pub fn parse(name: &str) -> Option<Self> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+  }
+}
+
+/// Builder for [`ProcessOptions`].
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOptionsBuilder {
+  options: ProcessOptions,
+}
+
+impl ProcessOptionsBuilder {
+  /// Include public items without doc comments.
+  pub fn include_undocumented(mut self, include: bool) -> Self {
+    self.options.include_undocumented = include;
+    self
+  }
+
+  /// Include crate-visible items and items of private modules.
+  pub fn includes_private(mut self, include: bool) -> Self {
+    self.options.includes_private = include;
+    self
+  }
+
+  /// Include items marked `#[deprecated]` (the default), or leave them out.
+  pub fn include_deprecated(mut self, include: bool) -> Self {
+    self.options.deprecated_items = if include {
+      DeprecatedItems::Include
+    } else {
+      DeprecatedItems::Exclude
+    };
+    self
+  }
+
+  /// Choose how items marked `#[deprecated]` are listed.
+  pub fn deprecated_items(mut self, mode: DeprecatedItems) -> Self {
+    self.options.deprecated_items = mode;
+    self
+  }
+
+  /// Include items marked `#[doc(hidden)]`.
+  pub fn include_hidden(mut self, include: bool) -> Self {
+    self.options.include_hidden = include;
+    self
+  }
+
+  /// List auto trait and blanket implementations under types (the default).
+  pub fn include_blanket_impls(mut self, include: bool) -> Self {
+    self.options.include_blanket_impls = include;
+    self
+  }
+
+  /// Only include items of the given kind; may be called several times.
+  pub fn include_kind(mut self, kind: ItemKind) -> Self {
+    self.options.include_kinds.push(kind);
+    self
+  }
+
+  /// Never include items of the given kind; may be called several times.
+  pub fn exclude_kind(mut self, kind: ItemKind) -> Self {
+    self.options.exclude_kinds.push(kind);
+    self
+  }
+
+  /// Only include items whose path matches the glob pattern; may be called
+  /// several times.
+  pub fn include_path(mut self, pattern: impl Into<String>) -> Self {
+    self.options.include_paths.push(pattern.into());
+    self
+  }
+
+  /// Never include items whose path matches the glob pattern; may be called
+  /// several times.
+  pub fn exclude_path(mut self, pattern: impl Into<String>) -> Self {
+    self.options.exclude_paths.push(pattern.into());
+    self
+  }
+
+  /// Limit the number of items listed after the crate root.
+  pub fn max_items(mut self, max: usize) -> Self {
+    self.options.max_items = Some(max);
+    self
+  }
+
+  /// Only include items whose required cargo features are all enabled.
+  pub fn enabled_features<I, S>(mut self, features: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    self.options.enabled_features =
+      Some(features.into_iter().map(Into::into).collect());
+    self
+  }
+
+  /// Build the options.
+  pub fn build(self) -> ProcessOptions {
+    self.options
+  }
+}
+
+/// Parse an item kind from its snake_case name, e.g. `struct` or
+/// `proc_derive`, as used in rustdoc JSON.
+///
+/// # Examples
+///
+/// ```
+/// use crates_llms_txt::options::{parse_item_kind, ItemKind};
+///
+/// assert_eq!(parse_item_kind("type_alias"), Some(ItemKind::TypeAlias));
+/// assert_eq!(parse_item_kind("class"), None);
+/// ```
+pub fn parse_item_kind(name: &str) -> Option<ItemKind> {
+  serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+/// Match a `::`-separated path against a glob pattern.
+fn glob_match(pattern: &str, path: &str) -> bool {
+  fn matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+      [] => path.is_empty(),
+      [b'*', b'*', rest @ ..] => {
+        (0..=path.len()).any(|skip| matches(rest, &path[skip..]))
+      }
+      [b'*', rest @ ..] => {
+        let segment = path.iter().take_while(|&&byte| byte != b':').count();
+        (0..=segment).any(|skip| matches(rest, &path[skip..]))
+      }
+      [byte, rest @ ..] => {
+        path.first() == Some(byte) && matches(rest, &path[1..])
+      }
+    }
+  }
+
+  matches(pattern.as_bytes(), path.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_glob_match() {
+    assert!(glob_match("demo::parser::Parser", "demo::parser::Parser"));
+    assert!(glob_match("demo::parser::*", "demo::parser::Parser"));
+    assert!(!glob_match("demo::*", "demo::parser::Parser"));
+    assert!(glob_match("demo::**", "demo::parser::Parser"));
+    assert!(glob_match("demo::*::Par*", "demo::parser::Parser"));
+    assert!(glob_match("**::new", "demo::parser::Parser::new"));
+    assert!(!glob_match("demo::parser", "demo::parser::Parser"));
+  }
+
+  #[test]
+  fn test_builder_filters() {
+    let options = ProcessOptions::builder()
+      .include_kind(ItemKind::Struct)
+      .include_kind(ItemKind::Function)
+      .exclude_kind(ItemKind::Function)
+      .include_path("demo::**")
+      .exclude_path("demo::internal::**")
+      .build();
+
+    assert!(options.allows_kind(ItemKind::Struct));
+    assert!(!options.allows_kind(ItemKind::Function));
+    assert!(!options.allows_kind(ItemKind::Enum));
+    assert!(options.allows_path("demo::parser::Parser"));
+    assert!(!options.allows_path("demo::internal::Helper"));
+    assert!(!options.allows_path("other::Parser"));
+
+    let defaults = ProcessOptions::default();
+    assert_eq!(ProcessOptions::builder().build(), defaults);
+    assert_eq!(defaults.deprecated_items, DeprecatedItems::Include);
+    assert!(defaults.allows_kind(ItemKind::Macro));
+    assert!(defaults.allows_path("anything::at::all"));
+    assert!(defaults.allows_features(&["json".to_string()]));
+    assert_eq!(
+      ProcessOptions::builder()
+        .include_deprecated(false)
+        .build()
+        .deprecated_items,
+      DeprecatedItems::Exclude
+    );
+    assert_eq!(
+      DeprecatedItems::parse("optional"),
+      Some(DeprecatedItems::Optional)
+    );
+    assert_eq!(DeprecatedItems::parse("hide"), None);
+
+    let options = ProcessOptions::builder().enabled_features(["json"]).build();
+    assert!(options.allows_features(&[]));
+    assert!(options.allows_features(&["json".to_string()]));
+    assert!(!options.allows_features(&["json".to_string(), "tls".to_string()]));
+  }
+}
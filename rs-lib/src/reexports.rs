@@ -0,0 +1,340 @@
+//! # Re-export Resolution
+//!
+//! Many crates define their items in private modules and expose them through
+//! `pub use` declarations, so the visibility of an item at its definition site
+//! says little about whether it is part of the public API. This module walks
+//! the module tree from the crate root, following `pub use` re-exports, and
+//! computes the public path of every reachable item. Members of types and
+//! traits, which have no path of their own, are given their parent's path.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rustdoc_types::{Id, ItemEnum, ItemSummary, StructKind};
+
+use crate::temp_trait::Item;
+
+/// Compute the public path of every item reachable from the crate root.
+///
+/// Items declared in a chain of public modules keep their declaration path.
+/// Items that are only reachable through re-exports are listed under the
+/// first re-export path found, walking the module tree breadth first. Items
+/// re-exported from other crates are not part of the index and are skipped.
+///
+/// # Arguments
+///
+/// * `root_id` - The id of the crate root module
+/// * `index` - The crate index, from `CommonCrates::index`
+/// * `paths` - The path summaries, from `CommonCrates::paths`
+///
+/// # Returns
+///
+/// * `HashMap<Id, Vec<String>>` - The public path of each reachable item,
+///   including the crate root itself
+pub fn public_paths(
+  root_id: &Id,
+  index: &HashMap<Id, Item>,
+  paths: &HashMap<Id, ItemSummary>,
+) -> HashMap<Id, Vec<String>> {
+  let root_path = match (paths.get(root_id), index.get(root_id)) {
+    (Some(summary), _) => summary.path.clone(),
+    (
+      None,
+      Some(Item {
+        name: Some(name), ..
+      }),
+    ) => vec![name.clone()],
+    _ => return HashMap::new(),
+  };
+
+  let mut walker = Walker {
+    index,
+    public: HashMap::from([(*root_id, root_path.clone())]),
+    visited: HashSet::from([*root_id]),
+  };
+
+  // First walk the declared module tree only, so that items living in public
+  // modules are recorded under their declaration path
+  let mut declared = Vec::new();
+  let mut queue = VecDeque::from([(*root_id, root_path)]);
+  while let Some((module_id, path)) = queue.pop_front() {
+    for (child, child_path) in walker.declared_children(&module_id, &path) {
+      if walker.is_module(&child) && walker.visited.insert(child) {
+        queue.push_back((child, child_path));
+      }
+    }
+    declared.push((module_id, path));
+  }
+
+  // Then follow re-exports. Modules of the declared tree only need their
+  // `use` items processed; modules reached through a re-export are walked
+  // in full under the path they were re-exported at
+  let mut queue = declared
+    .into_iter()
+    .map(|(module_id, path)| (module_id, path, false))
+    .collect::<VecDeque<_>>();
+  while let Some((module_id, path, walk_declared)) = queue.pop_front() {
+    let mut reached = walker.reexported_children(&module_id, &path);
+    if walk_declared {
+      reached.extend(walker.declared_children(&module_id, &path));
+    }
+    for (child, child_path) in reached {
+      if walker.is_module(&child) && walker.visited.insert(child) {
+        queue.push_back((child, child_path, true));
+      }
+    }
+  }
+
+  walker.public
+}
+
+/// Compute the path of the members of types and traits.
+///
+/// Fields, variants, associated items and methods of inherent and trait
+/// impls have no entry in `CommonCrates::paths`; their path is the path of
+/// their parent followed by their name, e.g. `clap::builder::Arg::new`.
+///
+/// # Arguments
+///
+/// * `index` - The crate index, from `CommonCrates::index`
+/// * `paths` - The path summaries, with public paths already applied
+///
+/// # Returns
+///
+/// * `HashMap<Id, Vec<String>>` - The path of each member
+pub fn member_paths(
+  index: &HashMap<Id, Item>,
+  paths: &HashMap<Id, ItemSummary>,
+) -> HashMap<Id, Vec<String>> {
+  member_parents(index, paths)
+    .into_iter()
+    .filter_map(|(member_id, parent_id)| {
+      let name = index.get(&member_id)?.name.clone()?;
+      let mut path = paths.get(&parent_id)?.path.clone();
+      path.push(name);
+      Some((member_id, path))
+    })
+    .collect()
+}
+
+/// Find the type or trait each member belongs to.
+///
+/// # Arguments
+///
+/// * `index` - The crate index, from `CommonCrates::index`
+/// * `paths` - The path summaries, with public paths already applied
+///
+/// # Returns
+///
+/// * `HashMap<Id, Id>` - The parent of each named field, variant, associated
+///   item and impl method
+pub fn member_parents(
+  index: &HashMap<Id, Item>,
+  paths: &HashMap<Id, ItemSummary>,
+) -> HashMap<Id, Id> {
+  let mut members = HashMap::new();
+
+  // Blanket impls are listed under every type they apply to; visit parents
+  // in path order so that shared members always get the same path
+  let mut parents = paths.iter().collect::<Vec<_>>();
+  parents.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+
+  for (id, _) in parents {
+    let Some(parent) = index.get(id) else {
+      continue;
+    };
+
+    let (direct, impls): (Vec<Id>, &[Id]) = match &parent.inner {
+      ItemEnum::Struct(struct_) => {
+        let fields = match &struct_.kind {
+          StructKind::Plain { fields, .. } => fields.clone(),
+          StructKind::Tuple(fields) => {
+            fields.iter().flatten().copied().collect()
+          }
+          StructKind::Unit => Vec::new(),
+        };
+        (fields, &struct_.impls)
+      }
+      ItemEnum::Union(union) => (union.fields.clone(), &union.impls),
+      ItemEnum::Enum(enum_) => (enum_.variants.clone(), &enum_.impls),
+      ItemEnum::Trait(trait_) => (trait_.items.clone(), &[]),
+      _ => continue,
+    };
+
+    let impl_items = impls.iter().filter_map(|impl_id| {
+      match index.get(impl_id).map(|impl_| &impl_.inner) {
+        Some(ItemEnum::Impl(impl_)) => Some(impl_.items.iter().copied()),
+        _ => None,
+      }
+    });
+    for member_id in direct.into_iter().chain(impl_items.flatten()) {
+      if index
+        .get(&member_id)
+        .is_some_and(|item| item.name.is_some())
+      {
+        members.entry(member_id).or_insert(*id);
+      }
+    }
+  }
+
+  members
+}
+
+/// State shared by the two walks over the module tree.
+struct Walker<'a> {
+  index: &'a HashMap<Id, Item>,
+  /// Public path of each reached item; the first path found wins
+  public: HashMap<Id, Vec<String>>,
+  /// Modules whose children have been, or are queued to be, walked
+  visited: HashSet<Id>,
+}
+
+impl Walker<'_> {
+  /// Record the items declared in a module, returning them with their paths.
+  fn declared_children(
+    &mut self,
+    module_id: &Id,
+    path: &[String],
+  ) -> Vec<(Id, Vec<String>)> {
+    let mut reached = Vec::new();
+    for child_id in self.module_items(module_id) {
+      let Some(child) = self.index.get(&child_id) else {
+        continue;
+      };
+      if matches!(child.inner, ItemEnum::Use(_)) {
+        continue;
+      }
+      if let Some(name) = &child.name {
+        reached.push(self.record(child_id, path, name));
+      }
+    }
+    reached
+  }
+
+  /// Record the targets of the `use` items of a module, returning them with
+  /// their paths. Glob imports of modules are expanded in place.
+  fn reexported_children(
+    &mut self,
+    module_id: &Id,
+    path: &[String],
+  ) -> Vec<(Id, Vec<String>)> {
+    let mut reached = Vec::new();
+    for child_id in self.module_items(module_id) {
+      let Some(ItemEnum::Use(import)) =
+        self.index.get(&child_id).map(|child| &child.inner)
+      else {
+        continue;
+      };
+      let Some(target) = import.id.filter(|id| self.index.contains_key(id))
+      else {
+        continue;
+      };
+
+      if import.is_glob {
+        if self.is_module(&target) && self.visited.insert(target) {
+          reached.extend(self.declared_children(&target, path));
+          reached.extend(self.reexported_children(&target, path));
+        }
+        continue;
+      }
+      reached.push(self.record(target, path, &import.name));
+    }
+    reached
+  }
+
+  /// Record `id` under `parent::name` unless it already has a public path.
+  fn record(
+    &mut self,
+    id: Id,
+    parent: &[String],
+    name: &str,
+  ) -> (Id, Vec<String>) {
+    let mut path = parent.to_vec();
+    path.push(name.to_string());
+    self.public.entry(id).or_insert_with(|| path.clone());
+    (id, path)
+  }
+
+  fn module_items(&self, module_id: &Id) -> Vec<Id> {
+    match self.index.get(module_id).map(|module| &module.inner) {
+      Some(ItemEnum::Module(module)) => module.items.clone(),
+      _ => Vec::new(),
+    }
+  }
+
+  fn is_module(&self, id: &Id) -> bool {
+    matches!(
+      self.index.get(id).map(|item| &item.inner),
+      Some(ItemEnum::Module(_))
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::fixture_crate;
+
+  fn path_of(public: &HashMap<Id, Vec<String>>, name: &str) -> Option<String> {
+    fixture_crate()
+      .index()
+      .values()
+      .filter(|item| item.name.as_deref() == Some(name))
+      .filter(|item| !matches!(item.inner, ItemEnum::Use(_)))
+      .find_map(|item| public.get(&item.id))
+      .map(|path| path.join("::"))
+  }
+
+  #[test]
+  fn test_public_paths_fixture() {
+    let krate = fixture_crate();
+    let public = public_paths(&krate.root, &krate.index(), &krate.paths);
+
+    assert_eq!(public.get(&krate.root).unwrap(), &vec!["demo".to_string()]);
+    // Declared in a private module, only reachable through `pub use`
+    assert_eq!(
+      path_of(&public, "Reexported").as_deref(),
+      Some("demo::Reexported")
+    );
+    // Re-exported at the root, but declared in a public module
+    assert_eq!(
+      path_of(&public, "Parser").as_deref(),
+      Some("demo::parser::Parser")
+    );
+    assert_eq!(path_of(&public, "parse").as_deref(), Some("demo::parse"));
+    assert_eq!(path_of(&public, "tokens").as_deref(), Some("demo::tokens"));
+  }
+
+  #[test]
+  fn test_public_paths_glob_reexport() {
+    let krate = fixture_crate();
+    let mut index = krate.index();
+    // Turn `pub use internal::Reexported` into `pub use parser::*`
+    let parser_module = index
+      .values()
+      .find(|item| {
+        item.name.as_deref() == Some("parser")
+          && matches!(item.inner, ItemEnum::Module(_))
+      })
+      .unwrap()
+      .id;
+    for item in index.values_mut() {
+      if let ItemEnum::Use(import) = &mut item.inner {
+        if import.name == "Reexported" {
+          import.id = Some(parser_module);
+          import.is_glob = true;
+        }
+      }
+    }
+
+    let public = public_paths(&krate.root, &index, &krate.paths);
+
+    // Already public at its declaration path
+    assert_eq!(
+      path_of(&public, "Parser").as_deref(),
+      Some("demo::parser::Parser")
+    );
+    // No longer re-exported
+    assert_eq!(path_of(&public, "Reexported"), None);
+  }
+}
@@ -0,0 +1,363 @@
+//! # llms.txt Rendering
+//!
+//! This module turns a processed [`CrateDocs`] into the text files described by
+//! the [llms.txt specification](https://llmstxt.org/):
+//!
+//! - **`llms.txt`**: An H1 title, a blockquote summary and H2 sections made of
+//!   `- [title](link): description` lines, noting deprecated items and the
+//!   cargo features an item requires
+//! - **`llms-full.txt`**: The complete documentation content of every item,
+//!   concatenated into a single markdown document, with a notice above
+//!   deprecated items
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustdoc_types::Deprecation;
+
+use crate::error::Result;
+use crate::CrateDocs;
+
+/// File name of the generated index file
+pub const LLMS_TXT_FILE_NAME: &str = "llms.txt";
+
+/// File name of the generated full content file
+pub const LLMS_FULL_TXT_FILE_NAME: &str = "llms-full.txt";
+
+/// Description shown in `llms.txt` for items without a doc comment
+const UNDOCUMENTED_DESCRIPTION: &str = "Undocumented.";
+
+/// Separator placed between items in `llms-full.txt`
+const FULL_ITEM_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Render the `llms.txt` index for a crate.
+///
+/// # Arguments
+///
+/// * `docs` - The processed crate documentation
+///
+/// # Returns
+///
+/// * `String` - The markdown content of the `llms.txt` file
+pub fn llms_txt(docs: &CrateDocs) -> String {
+  let mut out = header(docs);
+
+  for section in docs.sections() {
+    out.push_str(&format!("\n## {}\n\n", section.title));
+    for session in &section.items {
+      let description = if session.undocumented {
+        UNDOCUMENTED_DESCRIPTION
+      } else {
+        &session.description
+      };
+      let description = match &session.deprecation {
+        Some(deprecation) => {
+          format!("{} {}", deprecation_notice(deprecation), description.trim())
+        }
+        None => description.to_string(),
+      };
+      let description = match features_note(&session.required_features) {
+        Some(note) => format!("{} {note}", description.trim()),
+        None => description,
+      };
+      out.push_str(&link_line(&session.title, &session.link, &description));
+    }
+  }
+
+  out
+}
+
+/// Render the `llms-full.txt` document for a crate.
+///
+/// # Arguments
+///
+/// * `docs` - The processed crate documentation
+///
+/// # Returns
+///
+/// * `String` - The markdown content of the `llms-full.txt` file
+pub fn llms_full_txt(docs: &CrateDocs) -> String {
+  let mut out = header(docs);
+
+  let items = docs
+    .full_sessions
+    .iter()
+    .map(|session| {
+      let notice = session
+        .deprecation
+        .as_ref()
+        .map(|deprecation| format!("> {}\n\n", deprecation_notice(deprecation)))
+        .unwrap_or_default();
+      format!(
+        "{notice}{}\n\nSource: <{}>",
+        session.content.trim_end(),
+        session.link
+      )
+    })
+    .collect::<Vec<_>>();
+
+  if !items.is_empty() {
+    out.push('\n');
+    out.push_str(&items.join(FULL_ITEM_SEPARATOR));
+    out.push('\n');
+  }
+
+  out
+}
+
+/// Write `llms.txt` and `llms-full.txt` into a directory.
+///
+/// The directory is created if it does not exist yet. Existing files with the
+/// same names are overwritten.
+///
+/// # Arguments
+///
+/// * `docs` - The processed crate documentation
+/// * `dir` - The output directory
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>>` - Paths of the written files
+///
+/// # Errors
+///
+/// * `Error::Io` - If the directory or one of the files cannot be written
+pub fn write_to_dir(docs: &CrateDocs, dir: &Path) -> Result<Vec<PathBuf>> {
+  fs::create_dir_all(dir)?;
+
+  let llms_txt_path = dir.join(LLMS_TXT_FILE_NAME);
+  fs::write(&llms_txt_path, llms_txt(docs))?;
+
+  let llms_full_txt_path = dir.join(LLMS_FULL_TXT_FILE_NAME);
+  fs::write(&llms_full_txt_path, llms_full_txt(docs))?;
+
+  Ok(vec![llms_txt_path, llms_full_txt_path])
+}
+
+/// Render the H1 title and blockquote summary shared by both files.
+fn header(docs: &CrateDocs) -> String {
+  let summary = docs
+    .sessions
+    .first()
+    .map(|root| root.description.trim())
+    .filter(|description| !description.is_empty())
+    .map(String::from)
+    .unwrap_or_else(|| {
+      format!(
+        "API documentation for the `{}` crate, version {}.",
+        docs.lib_name, docs.version
+      )
+    });
+
+  format!("# {}\n\n> {}\n", docs.lib_name, blockquote(&summary))
+}
+
+/// Render a single `- [title](link): description` line.
+fn link_line(title: &str, link: &str, description: &str) -> String {
+  let description = description.trim();
+  if description.is_empty() {
+    format!("- [{title}]({link})\n")
+  } else {
+    format!("- [{title}]({link}): {description}\n")
+  }
+}
+
+/// Describe the deprecation of an item, e.g. "**Deprecated** since 0.2.0: use
+/// `parse` instead."
+pub(crate) fn deprecation_notice(deprecation: &Deprecation) -> String {
+  let mut notice = "**Deprecated**".to_string();
+  if let Some(since) = &deprecation.since {
+    notice.push_str(&format!(" since {since}"));
+  }
+  match deprecation.note.as_deref().map(str::trim) {
+    Some(note) if !note.is_empty() => {
+      notice.push_str(&format!(": {}.", note.trim_end_matches('.')))
+    }
+    _ if deprecation.since.is_none() => notice = "**Deprecated.**".into(),
+    _ => notice.push('.'),
+  }
+  notice
+}
+
+/// Describe the cargo features an item requires, e.g. "Requires feature
+/// `json`.", or `None` if it is not feature-gated.
+fn features_note(features: &[String]) -> Option<String> {
+  let names = features
+    .iter()
+    .map(|feature| format!("`{feature}`"))
+    .collect::<Vec<_>>();
+  match names.as_slice() {
+    [] => None,
+    [name] => Some(format!("Requires feature {name}.")),
+    names => Some(format!("Requires features {}.", names.join(", "))),
+  }
+}
+
+/// Continue a blockquote across every line of a multi-line string.
+fn blockquote(text: &str) -> String {
+  text.lines().collect::<Vec<_>>().join("\n> ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::{DeprecatedItems, ProcessOptions};
+  use crate::sections::ItemCategory;
+  use crate::tests::{process, session, titles};
+  use crate::{FullSessionItem, SessionItem};
+
+  fn sample_docs() -> CrateDocs {
+    let mut docs = CrateDocs::new("demo", "1.2.3");
+    docs.sessions.push(SessionItem {
+      title: "demo".to_string(),
+      description: "".to_string(),
+      link: "https://docs.rs/demo/1.2.3".to_string(),
+      source_link: None,
+      undocumented: false,
+      attributes: Default::default(),
+      required_features: Vec::new(),
+      deprecation: None,
+      optional: false,
+      category: ItemCategory::Module,
+      module: "demo".to_string(),
+    });
+    docs.sessions.push(SessionItem {
+      title: "Parser".to_string(),
+      description: "Parses things.".to_string(),
+      link: "https://docs.rs/demo/1.2.3/demo/struct.Parser.html".to_string(),
+      source_link: None,
+      undocumented: false,
+      attributes: Default::default(),
+      required_features: Vec::new(),
+      deprecation: None,
+      optional: false,
+      category: ItemCategory::Struct,
+      module: "demo".to_string(),
+    });
+    docs.sessions.push(SessionItem {
+      title: "new".to_string(),
+      description: "Creates a parser.".to_string(),
+      link: "https://docs.rs/crate/demo/1.2.3/source/src/lib.rs#7".to_string(),
+      source_link: None,
+      undocumented: false,
+      attributes: Default::default(),
+      required_features: vec!["json".to_string()],
+      deprecation: None,
+      optional: false,
+      category: ItemCategory::Other,
+      module: String::new(),
+    });
+    docs.full_sessions.push(FullSessionItem {
+      content: "Parses things.\n\nWith more detail.\n".to_string(),
+      link: "https://docs.rs/demo/1.2.3/demo/struct.Parser.html".to_string(),
+      source_link: None,
+      undocumented: false,
+      attributes: Default::default(),
+      required_features: Vec::new(),
+      deprecation: None,
+    });
+    docs
+  }
+
+  #[test]
+  fn test_llms_txt_layout() {
+    let txt = llms_txt(&sample_docs());
+
+    assert!(txt.starts_with("# demo\n\n> "));
+    assert!(txt.contains(
+      "\n## demo: Modules\n\n- [demo](https://docs.rs/demo/1.2.3)\n"
+    ));
+    assert!(txt.contains(
+      "\n## demo: Structs\n\n- [Parser](https://docs.rs/demo/1.2.3/demo/struct.Parser.html): Parses things.\n"
+    ));
+    assert!(txt.ends_with(
+      "\n## Optional\n\n- [new](https://docs.rs/crate/demo/1.2.3/source/src/lib.rs#7): Creates a parser. Requires feature `json`.\n"
+    ));
+  }
+
+  #[test]
+  fn test_deprecation_notice() {
+    let deprecation = |since: Option<&str>, note: Option<&str>| Deprecation {
+      since: since.map(String::from),
+      note: note.map(String::from),
+    };
+
+    assert_eq!(
+      deprecation_notice(&deprecation(Some("0.2.0"), Some("use `parse`."))),
+      "**Deprecated** since 0.2.0: use `parse`."
+    );
+    assert_eq!(
+      deprecation_notice(&deprecation(None, None)),
+      "**Deprecated.**"
+    );
+  }
+
+  #[test]
+  fn test_features_note() {
+    assert_eq!(features_note(&[]), None);
+    assert_eq!(
+      features_note(&["json".to_string(), "tls".to_string()]).as_deref(),
+      Some("Requires features `json`, `tls`.")
+    );
+  }
+
+  #[test]
+  fn test_llms_full_txt_contains_content() {
+    let txt = llms_full_txt(&sample_docs());
+
+    assert!(txt.starts_with("# demo\n"));
+    assert!(txt.contains("Parses things.\n\nWith more detail."));
+    assert!(txt.contains(
+      "Source: <https://docs.rs/demo/1.2.3/demo/struct.Parser.html>"
+    ));
+  }
+
+  #[test]
+  fn test_write_to_dir() {
+    let dir = std::env::temp_dir()
+      .join(format!("crates_llms_txt_render_{}", std::process::id()));
+    let paths = write_to_dir(&sample_docs(), &dir).unwrap();
+
+    assert_eq!(paths.len(), 2);
+    assert_eq!(
+      fs::read_to_string(dir.join(LLMS_TXT_FILE_NAME)).unwrap(),
+      llms_txt(&sample_docs())
+    );
+    assert!(dir.join(LLMS_FULL_TXT_FILE_NAME).exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_process_docs_deprecation() {
+    let process = |mode: DeprecatedItems| {
+      process(&ProcessOptions::builder().deprecated_items(mode).build())
+    };
+
+    let docs = process(DeprecatedItems::Include);
+    let (old_parse, old_parse_full) = session(&docs, "demo::old_parse");
+    let deprecation = old_parse.deprecation.as_ref().unwrap();
+    assert_eq!(deprecation.since.as_deref(), Some("0.2.0"));
+    assert_eq!(deprecation.note.as_deref(), Some("use `parse` instead"));
+    assert!(!old_parse.optional);
+    assert_eq!(old_parse_full.deprecation.as_ref(), Some(deprecation));
+    assert!(docs.to_llms_txt().contains(
+      "/fn.old_parse.html): **Deprecated** since 0.2.0: use `parse` instead."
+    ));
+    assert!(docs
+      .to_llms_full_txt()
+      .contains("> **Deprecated** since 0.2.0: use `parse` instead.\n\n"));
+
+    let docs = process(DeprecatedItems::Optional);
+    assert!(session(&docs, "demo::old_parse").0.optional);
+    let optional = docs.sections().pop().unwrap();
+    assert!(optional.optional);
+    assert!(optional
+      .items
+      .iter()
+      .any(|item| item.title == "demo::old_parse"));
+
+    let docs = process(DeprecatedItems::Exclude);
+    assert!(!titles(&docs).contains(&"demo::old_parse"));
+  }
+}
@@ -0,0 +1,339 @@
+//! # Section Model
+//!
+//! This module groups documentation sessions into the H2 sections of an
+//! `llms.txt` file. Items are grouped by the module they live in, in module
+//! tree order, and within a module by category (modules, structs, enums,
+//! traits, functions, macros, type aliases and constants). Low-priority items
+//! that have no page of their own are collected in a trailing "Optional"
+//! section, as described by the llms.txt specification.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use rustdoc_types::{ItemEnum, ItemKind, ItemSummary, MacroKind};
+use serde::{Deserialize, Serialize};
+
+use crate::SessionItem;
+
+/// Title of the section holding low-priority items
+pub const OPTIONAL_SECTION_TITLE: &str = "Optional";
+
+/// Coarse category of a documentation item, used to build sections.
+///
+/// The declaration order is the order in which sections are rendered.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Hash,
+  Serialize,
+  Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemCategory {
+  /// Modules, including the crate root
+  Module,
+  /// Structs and unions
+  Struct,
+  /// Enums
+  Enum,
+  /// Traits and trait aliases
+  Trait,
+  /// Free functions
+  Function,
+  /// Declarative and procedural macros
+  Macro,
+  /// Type aliases and extern types
+  TypeAlias,
+  /// Constants and statics
+  Constant,
+  /// Everything else (trait items, primitives, ...)
+  Other,
+}
+
+impl ItemCategory {
+  /// Map a rustdoc item kind to its category.
+  pub fn from_kind(kind: &ItemKind) -> Self {
+    match kind {
+      ItemKind::Module => ItemCategory::Module,
+      ItemKind::Struct | ItemKind::Union => ItemCategory::Struct,
+      ItemKind::Enum => ItemCategory::Enum,
+      ItemKind::Trait | ItemKind::TraitAlias => ItemCategory::Trait,
+      ItemKind::Function => ItemCategory::Function,
+      ItemKind::Macro | ItemKind::ProcAttribute | ItemKind::ProcDerive => {
+        ItemCategory::Macro
+      }
+      ItemKind::TypeAlias | ItemKind::ExternType => ItemCategory::TypeAlias,
+      ItemKind::Constant | ItemKind::Static => ItemCategory::Constant,
+      _ => ItemCategory::Other,
+    }
+  }
+
+  /// Human-readable plural name, used in section titles.
+  pub fn title(&self) -> &'static str {
+    match self {
+      ItemCategory::Module => "Modules",
+      ItemCategory::Struct => "Structs",
+      ItemCategory::Enum => "Enums",
+      ItemCategory::Trait => "Traits",
+      ItemCategory::Function => "Functions",
+      ItemCategory::Macro => "Macros",
+      ItemCategory::TypeAlias => "Type aliases",
+      ItemCategory::Constant => "Constants",
+      ItemCategory::Other => "Other items",
+    }
+  }
+
+  /// Stable lowercase identifier, matching the serialized form.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ItemCategory::Module => "module",
+      ItemCategory::Struct => "struct",
+      ItemCategory::Enum => "enum",
+      ItemCategory::Trait => "trait",
+      ItemCategory::Function => "function",
+      ItemCategory::Macro => "macro",
+      ItemCategory::TypeAlias => "type_alias",
+      ItemCategory::Constant => "constant",
+      ItemCategory::Other => "other",
+    }
+  }
+}
+
+/// Kind of an item, derived from its content.
+///
+/// Unlike the kinds found in `CommonCrates::paths`, this is available for every
+/// item of the index, including methods, fields and associated items.
+pub fn item_kind(inner: &ItemEnum) -> ItemKind {
+  match inner {
+    ItemEnum::Module(_) => ItemKind::Module,
+    ItemEnum::ExternCrate { .. } => ItemKind::ExternCrate,
+    ItemEnum::Use(_) => ItemKind::Use,
+    ItemEnum::Union(_) => ItemKind::Union,
+    ItemEnum::Struct(_) => ItemKind::Struct,
+    ItemEnum::StructField(_) => ItemKind::StructField,
+    ItemEnum::Enum(_) => ItemKind::Enum,
+    ItemEnum::Variant(_) => ItemKind::Variant,
+    ItemEnum::Function(_) => ItemKind::Function,
+    ItemEnum::Trait(_) => ItemKind::Trait,
+    ItemEnum::TraitAlias(_) => ItemKind::TraitAlias,
+    ItemEnum::Impl(_) => ItemKind::Impl,
+    ItemEnum::TypeAlias(_) => ItemKind::TypeAlias,
+    ItemEnum::Constant { .. } => ItemKind::Constant,
+    ItemEnum::Static(_) => ItemKind::Static,
+    ItemEnum::ExternType => ItemKind::ExternType,
+    ItemEnum::Macro(_) => ItemKind::Macro,
+    ItemEnum::ProcMacro(proc_macro) => match proc_macro.kind {
+      MacroKind::Bang => ItemKind::Macro,
+      MacroKind::Attr => ItemKind::ProcAttribute,
+      MacroKind::Derive => ItemKind::ProcDerive,
+    },
+    ItemEnum::Primitive(_) => ItemKind::Primitive,
+    ItemEnum::AssocConst { .. } => ItemKind::AssocConst,
+    ItemEnum::AssocType { .. } => ItemKind::AssocType,
+  }
+}
+
+/// Compute the module an item is listed under from its path summary.
+///
+/// The crate root is listed under itself, enum variants under the module of
+/// their enum and every other item under its parent module.
+pub fn parent_module(summary: &ItemSummary) -> String {
+  let parents = match (summary.kind, summary.path.len()) {
+    (ItemKind::Module, 1) => &summary.path[..],
+    (ItemKind::Variant, len) => &summary.path[..len.saturating_sub(2)],
+    (_, len) => &summary.path[..len.saturating_sub(1)],
+  };
+  parents.join("::")
+}
+
+/// Total order used to sort sessions deterministically.
+///
+/// Items with a module come first, in module tree order (parents before
+/// children), then by category and title. Items without a module (trait
+/// items, ...) follow, ordered by title. Links break the remaining ties.
+pub fn session_order(a: &SessionItem, b: &SessionItem) -> Ordering {
+  let key = |session: &SessionItem| {
+    (
+      session.module.is_empty() || session.category == ItemCategory::Other,
+      session
+        .module
+        .split("::")
+        .filter(|part| !part.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>(),
+      session.category,
+    )
+  };
+
+  key(a)
+    .cmp(&key(b))
+    .then_with(|| a.title.cmp(&b.title))
+    .then_with(|| a.link.cmp(&b.link))
+}
+
+/// A group of sessions rendered as one H2 section of `llms.txt`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+  /// Section title, e.g. `demo::parser: Structs` or `Optional`
+  pub title: String,
+  /// Module path shared by the items, or `None` for the optional section
+  pub module: Option<String>,
+  /// Category shared by the items, or `None` for the optional section
+  pub category: Option<ItemCategory>,
+  /// Whether this is the low-priority "Optional" section
+  pub optional: bool,
+  /// Items of the section, in session order
+  pub items: Vec<SessionItem>,
+}
+
+/// Group sessions into sections.
+///
+/// Sections are ordered by module path (parents before children) and then by
+/// category. Items in the `Other` category, without a module, or marked as
+/// optional end up in a single trailing "Optional" section.
+///
+/// # Arguments
+///
+/// * `sessions` - The sessions to group
+///
+/// # Returns
+///
+/// * `Vec<Section>` - The sections, with the optional section last if present
+pub fn group_sessions(sessions: &[SessionItem]) -> Vec<Section> {
+  let mut groups: BTreeMap<(Vec<&str>, ItemCategory), Vec<SessionItem>> =
+    BTreeMap::new();
+  let mut optional = Vec::new();
+
+  for session in sessions {
+    if session.optional
+      || session.category == ItemCategory::Other
+      || session.module.is_empty()
+    {
+      optional.push(session.clone());
+      continue;
+    }
+    let module = session.module.split("::").collect::<Vec<_>>();
+    groups
+      .entry((module, session.category))
+      .or_default()
+      .push(session.clone());
+  }
+
+  let mut sections = groups
+    .into_iter()
+    .map(|((module, category), items)| {
+      let module = module.join("::");
+      Section {
+        title: format!("{module}: {}", category.title()),
+        module: Some(module),
+        category: Some(category),
+        optional: false,
+        items,
+      }
+    })
+    .collect::<Vec<_>>();
+
+  if !optional.is_empty() {
+    sections.push(Section {
+      title: OPTIONAL_SECTION_TITLE.to_string(),
+      module: None,
+      category: None,
+      optional: true,
+      items: optional,
+    });
+  }
+
+  sections
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn session(title: &str, module: &str, category: ItemCategory) -> SessionItem {
+    SessionItem {
+      title: title.to_string(),
+      description: String::new(),
+      link: format!("https://docs.rs/{title}"),
+      source_link: None,
+      category,
+      module: module.to_string(),
+      undocumented: false,
+      attributes: Default::default(),
+      required_features: Vec::new(),
+      deprecation: None,
+      optional: false,
+    }
+  }
+
+  #[test]
+  fn test_group_sessions_order() {
+    let sessions = vec![
+      session("demo::parser::Parser", "demo::parser", ItemCategory::Struct),
+      session("demo::parse", "demo", ItemCategory::Function),
+      session("new", "", ItemCategory::Other),
+      session("demo::parser", "demo", ItemCategory::Module),
+      session("demo", "demo", ItemCategory::Module),
+      session("demo::Error", "demo", ItemCategory::Struct),
+    ];
+
+    let titles = group_sessions(&sessions)
+      .into_iter()
+      .map(|section| (section.title, section.items.len()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      titles,
+      vec![
+        ("demo: Modules".to_string(), 2),
+        ("demo: Structs".to_string(), 1),
+        ("demo: Functions".to_string(), 1),
+        ("demo::parser: Structs".to_string(), 1),
+        ("Optional".to_string(), 1),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_parent_module() {
+    let summary = |path: &[&str], kind| ItemSummary {
+      crate_id: 0,
+      path: path.iter().map(|part| part.to_string()).collect(),
+      kind,
+    };
+
+    assert_eq!(parent_module(&summary(&["demo"], ItemKind::Module)), "demo");
+    assert_eq!(
+      parent_module(&summary(&["demo", "parser"], ItemKind::Module)),
+      "demo"
+    );
+    assert_eq!(
+      parent_module(&summary(
+        &["demo", "parser", "Token", "Eof"],
+        ItemKind::Variant
+      )),
+      "demo::parser"
+    );
+  }
+
+  #[test]
+  fn test_category_from_kind() {
+    assert_eq!(
+      ItemCategory::from_kind(&ItemKind::Union),
+      ItemCategory::Struct
+    );
+    assert_eq!(
+      ItemCategory::from_kind(&ItemKind::ProcDerive),
+      ItemCategory::Macro
+    );
+    assert_eq!(
+      ItemCategory::from_kind(&ItemKind::Variant),
+      ItemCategory::Other
+    );
+  }
+}
@@ -0,0 +1,959 @@
+//! # Signature Rendering
+//!
+//! This module pretty-prints the type information carried by rustdoc JSON
+//! (`ItemEnum`, `Type`, `Generics`, ...) back into Rust source syntax, so that
+//! generated documentation can show declarations such as
+//! `pub async fn from_online(lib_name: &str, version: Option<String>) -> Result<CrateDocs>`.
+//!
+//! The output follows the layout used by rustdoc's HTML pages: `where` clauses
+//! are placed on their own lines and bodies of structs, enums and traits are
+//! indented by four spaces. Function and method bodies are never shown.
+
+use std::collections::HashMap;
+
+use rustdoc_types::{
+  Abi, AssocItemConstraint, AssocItemConstraintKind, FunctionHeader,
+  FunctionPointer, FunctionSignature, GenericArg, GenericArgs, GenericBound,
+  GenericParamDef, GenericParamDefKind, Generics, Id, ItemEnum, MacroKind,
+  Path, PolyTrait, PreciseCapturingArg, StructKind, Term, TraitBoundModifier,
+  Type, VariantKind, Visibility, WherePredicate,
+};
+
+use crate::macros;
+use crate::temp_trait::Item;
+
+/// Indentation used for members of structs, enums and traits
+const INDENT: &str = "    ";
+
+/// Render the declaration of an item as Rust source.
+///
+/// # Arguments
+///
+/// * `item` - The item to render
+/// * `index` - The crate index, used to look up fields, variants and
+///   associated items
+///
+/// # Returns
+///
+/// * `Option<String>` - The declaration, or `None` for items without a
+///   meaningful signature (primitives)
+pub fn item_signature(
+  item: &Item,
+  index: &HashMap<Id, Item>,
+) -> Option<String> {
+  let name = item.name.as_deref().unwrap_or("_");
+  let vis = visibility(&item.visibility);
+
+  let signature = match &item.inner {
+    ItemEnum::Module(_) => format!("{vis}mod {name}"),
+    ItemEnum::ExternCrate { name, rename } => match rename {
+      Some(rename) => format!("{vis}extern crate {name} as {rename};"),
+      None => format!("{vis}extern crate {name};"),
+    },
+    ItemEnum::Use(use_) => {
+      if use_.is_glob {
+        format!("{vis}use {}::*;", use_.source)
+      } else if use_.source.rsplit("::").next() == Some(use_.name.as_str()) {
+        format!("{vis}use {};", use_.source)
+      } else {
+        format!("{vis}use {} as {};", use_.source, use_.name)
+      }
+    }
+    ItemEnum::Union(union) => format!(
+      "{vis}union {name}{}{}",
+      generic_params(&union.generics),
+      fields_block(
+        &union.fields,
+        union.has_stripped_fields,
+        &union.generics,
+        index
+      )
+    ),
+    ItemEnum::Struct(struct_) => {
+      let params = generic_params(&struct_.generics);
+      match &struct_.kind {
+        StructKind::Unit => {
+          format!(
+            "{vis}struct {name}{params}{};",
+            final_where_clause(&struct_.generics)
+          )
+        }
+        StructKind::Tuple(fields) => format!(
+          "{vis}struct {name}{params}({}){};",
+          tuple_fields(fields, index, true),
+          final_where_clause(&struct_.generics)
+        ),
+        StructKind::Plain {
+          fields,
+          has_stripped_fields,
+        } => format!(
+          "{vis}struct {name}{params}{}",
+          fields_block(fields, *has_stripped_fields, &struct_.generics, index)
+        ),
+      }
+    }
+    ItemEnum::StructField(type_) => {
+      format!("{vis}{name}: {}", type_to_string(type_))
+    }
+    ItemEnum::Enum(enum_) => {
+      let mut out = format!(
+        "{vis}enum {name}{}{}",
+        generic_params(&enum_.generics),
+        where_clause(&enum_.generics)
+      );
+      out.push_str(if enum_.generics.where_predicates.is_empty() {
+        " {\n"
+      } else {
+        "\n{\n"
+      });
+      for variant in enum_.variants.iter().filter_map(|id| index.get(id)) {
+        if let Some(line) = variant_to_string(variant, index) {
+          out.push_str(&format!("{INDENT}{line},\n"));
+        }
+      }
+      if enum_.has_stripped_variants {
+        out.push_str(&format!("{INDENT}// some variants omitted\n"));
+      }
+      out.push('}');
+      out
+    }
+    ItemEnum::Variant(_) => variant_to_string(item, index)?,
+    ItemEnum::Function(function) => function_to_string(
+      &vis,
+      name,
+      &function.header,
+      &function.generics,
+      &function.sig,
+    ),
+    ItemEnum::Trait(trait_) => {
+      let mut out = format!(
+        "{vis}{}{}trait {name}{}",
+        if trait_.is_unsafe { "unsafe " } else { "" },
+        if trait_.is_auto { "auto " } else { "" },
+        generic_params(&trait_.generics)
+      );
+      if !trait_.bounds.is_empty() {
+        out.push_str(&format!(": {}", bounds_to_string(&trait_.bounds)));
+      }
+      out.push_str(&where_clause(&trait_.generics));
+
+      let members = trait_
+        .items
+        .iter()
+        .filter_map(|id| index.get(id))
+        .filter_map(|member| {
+          let signature = item_signature(member, index)?;
+          Some(match &member.inner {
+            ItemEnum::Function(function) if function.has_body => {
+              format!("{signature} {{ ... }}")
+            }
+            // A required method's `where` clause ends at the `;`
+            _ => {
+              format!("{};", signature.strip_suffix(',').unwrap_or(&signature))
+            }
+          })
+        })
+        .collect::<Vec<_>>();
+
+      if members.is_empty() {
+        out.push_str(" {}");
+      } else {
+        out.push_str(if trait_.generics.where_predicates.is_empty() {
+          " {\n"
+        } else {
+          "\n{\n"
+        });
+        for member in members {
+          out.push_str(&indent(&member));
+          out.push('\n');
+        }
+        out.push('}');
+      }
+      out
+    }
+    ItemEnum::TraitAlias(alias) => format!(
+      "{vis}trait {name}{} = {}{};",
+      generic_params(&alias.generics),
+      bounds_to_string(&alias.params),
+      final_where_clause(&alias.generics)
+    ),
+    ItemEnum::Impl(impl_) => {
+      let mut out = format!(
+        "{}impl{} ",
+        if impl_.is_unsafe { "unsafe " } else { "" },
+        generic_params(&impl_.generics)
+      );
+      if let Some(trait_) = &impl_.trait_ {
+        if impl_.is_negative {
+          out.push('!');
+        }
+        out.push_str(&format!("{} for ", path_to_string(trait_)));
+      }
+      out.push_str(&type_to_string(&impl_.for_));
+      out.push_str(&where_clause(&impl_.generics));
+      out
+    }
+    ItemEnum::TypeAlias(alias) => format!(
+      "{vis}type {name}{}{} = {};",
+      generic_params(&alias.generics),
+      final_where_clause(&alias.generics),
+      type_to_string(&alias.type_)
+    ),
+    ItemEnum::Constant { type_, const_ } => format!(
+      "{vis}const {name}: {} = {};",
+      type_to_string(type_),
+      const_.expr
+    ),
+    ItemEnum::Static(static_) => format!(
+      "{vis}{}static {}{name}: {} = {};",
+      if static_.is_unsafe { "unsafe " } else { "" },
+      if static_.is_mutable { "mut " } else { "" },
+      type_to_string(&static_.type_),
+      static_.expr
+    ),
+    ItemEnum::ExternType => format!("{vis}type {name};"),
+    // Rules are shown by their matchers, their expansion elided as rustdoc
+    // does
+    ItemEnum::Macro(source) => match macros::macro_rules_matchers(source) {
+      Some((name, matchers)) if !matchers.is_empty() => {
+        let mut out = format!("macro_rules! {name} {{\n");
+        for matcher in matchers {
+          out.push_str(&indent(&format!("{matcher} => {{ ... }};")));
+          out.push('\n');
+        }
+        out.push('}');
+        out
+      }
+      _ => source.trim().to_string(),
+    },
+    ItemEnum::ProcMacro(proc_macro) => match proc_macro.kind {
+      MacroKind::Bang => format!("{name}!() {{ /* proc-macro */ }}"),
+      MacroKind::Attr => format!("#[{name}]"),
+      MacroKind::Derive if proc_macro.helpers.is_empty() => {
+        format!("#[derive({name})]")
+      }
+      MacroKind::Derive => {
+        let mut out = format!(
+          "#[derive({name})]\n{{\n{INDENT}// Attributes available to this derive:\n"
+        );
+        for helper in &proc_macro.helpers {
+          out.push_str(&format!("{INDENT}#[{helper}]\n"));
+        }
+        out.push('}');
+        out
+      }
+    },
+    ItemEnum::Primitive(_) => return None,
+    ItemEnum::AssocConst { type_, value } => match value {
+      Some(value) => {
+        format!("const {name}: {} = {value}", type_to_string(type_))
+      }
+      None => format!("const {name}: {}", type_to_string(type_)),
+    },
+    ItemEnum::AssocType {
+      generics,
+      bounds,
+      type_,
+    } => {
+      let mut out = format!("type {name}{}", generic_params(generics));
+      if !bounds.is_empty() {
+        out.push_str(&format!(": {}", bounds_to_string(bounds)));
+      }
+      out.push_str(&final_where_clause(generics));
+      if let Some(type_) = type_ {
+        out.push_str(&format!(" = {}", type_to_string(type_)));
+      }
+      out
+    }
+  };
+
+  Some(signature)
+}
+
+/// Render a type as Rust source.
+///
+/// # Arguments
+///
+/// * `type_` - The type to render
+///
+/// # Returns
+///
+/// * `String` - The type, e.g. `Option<&'a str>`
+pub fn type_to_string(type_: &Type) -> String {
+  match type_ {
+    Type::ResolvedPath(path) => path_to_string(path),
+    Type::DynTrait(dyn_trait) => {
+      let mut parts = dyn_trait
+        .traits
+        .iter()
+        .map(poly_trait_to_string)
+        .collect::<Vec<_>>();
+      if let Some(lifetime) = &dyn_trait.lifetime {
+        parts.push(lifetime.clone());
+      }
+      format!("dyn {}", parts.join(" + "))
+    }
+    Type::Generic(name) | Type::Primitive(name) => name.clone(),
+    Type::FunctionPointer(pointer) => function_pointer_to_string(pointer),
+    Type::Tuple(types) => match types.as_slice() {
+      [single] => format!("({},)", type_to_string(single)),
+      types => format!("({})", join(types.iter().map(type_to_string))),
+    },
+    Type::Slice(inner) => format!("[{}]", type_to_string(inner)),
+    Type::Array { type_, len } => format!("[{}; {len}]", type_to_string(type_)),
+    Type::Pat { type_, .. } => type_to_string(type_),
+    Type::ImplTrait(bounds) => format!("impl {}", bounds_to_string(bounds)),
+    Type::Infer => "_".to_string(),
+    Type::RawPointer { is_mutable, type_ } => format!(
+      "*{} {}",
+      if *is_mutable { "mut" } else { "const" },
+      type_to_string(type_)
+    ),
+    Type::BorrowedRef {
+      lifetime,
+      is_mutable,
+      type_,
+    } => {
+      let mut out = String::from("&");
+      if let Some(lifetime) = lifetime {
+        out.push_str(lifetime);
+        out.push(' ');
+      }
+      if *is_mutable {
+        out.push_str("mut ");
+      }
+      out.push_str(&type_to_string(type_));
+      out
+    }
+    Type::QualifiedPath {
+      name,
+      args,
+      self_type,
+      trait_,
+    } => {
+      let args = args
+        .as_deref()
+        .map(generic_args_to_string)
+        .unwrap_or_default();
+      match trait_ {
+        Some(trait_) if !trait_.path.is_empty() => format!(
+          "<{} as {}>::{name}{args}",
+          type_to_string(self_type),
+          path_to_string(trait_)
+        ),
+        _ => format!("{}::{name}{args}", type_to_string(self_type)),
+      }
+    }
+  }
+}
+
+/// Render generic parameters, e.g. `<'a, T: Clone, const N: usize>`.
+///
+/// Synthetic parameters introduced by `impl Trait` arguments are omitted.
+pub fn generic_params(generics: &Generics) -> String {
+  let params = generics
+    .params
+    .iter()
+    .filter(|param| {
+      !matches!(
+        param.kind,
+        GenericParamDefKind::Type {
+          is_synthetic: true,
+          ..
+        }
+      )
+    })
+    .map(generic_param_to_string)
+    .collect::<Vec<_>>();
+
+  if params.is_empty() {
+    String::new()
+  } else {
+    format!("<{}>", params.join(", "))
+  }
+}
+
+/// Render a `where` clause on its own lines, or nothing if there is none.
+pub fn where_clause(generics: &Generics) -> String {
+  if generics.where_predicates.is_empty() {
+    return String::new();
+  }
+
+  let predicates = generics
+    .where_predicates
+    .iter()
+    .map(|predicate| {
+      format!("{INDENT}{},", where_predicate_to_string(predicate))
+    })
+    .collect::<Vec<_>>();
+
+  format!("\nwhere\n{}", predicates.join("\n"))
+}
+
+/// Render a `where` clause followed by `;` or `=`, without the separator
+/// after its last predicate.
+fn final_where_clause(generics: &Generics) -> String {
+  let clause = where_clause(generics);
+  clause.strip_suffix(',').unwrap_or(&clause).to_string()
+}
+
+/// Render a list of bounds, e.g. `Clone + Send + 'static`.
+pub fn bounds_to_string(bounds: &[GenericBound]) -> String {
+  bounds
+    .iter()
+    .map(bound_to_string)
+    .collect::<Vec<_>>()
+    .join(" + ")
+}
+
+/// Render a resolved path with its generic arguments.
+pub fn path_to_string(path: &Path) -> String {
+  let args = path
+    .args
+    .as_deref()
+    .map(generic_args_to_string)
+    .unwrap_or_default();
+  format!("{}{args}", path.path)
+}
+
+fn visibility(visibility: &Visibility) -> String {
+  match visibility {
+    Visibility::Public => "pub ".to_string(),
+    Visibility::Default => String::new(),
+    Visibility::Crate => "pub(crate) ".to_string(),
+    Visibility::Restricted { path, .. } => format!("pub(in {path}) "),
+  }
+}
+
+fn function_to_string(
+  vis: &str,
+  name: &str,
+  header: &FunctionHeader,
+  generics: &Generics,
+  sig: &FunctionSignature,
+) -> String {
+  format!(
+    "{vis}{}fn {name}{}({}){}{}",
+    header_to_string(header),
+    generic_params(generics),
+    inputs_to_string(sig),
+    output_to_string(sig),
+    where_clause(generics)
+  )
+}
+
+fn function_pointer_to_string(pointer: &FunctionPointer) -> String {
+  let for_params = if pointer.generic_params.is_empty() {
+    String::new()
+  } else {
+    format!(
+      "for<{}> ",
+      join(pointer.generic_params.iter().map(generic_param_to_string))
+    )
+  };
+  let inputs = join(
+    pointer
+      .sig
+      .inputs
+      .iter()
+      .map(|(_, type_)| type_to_string(type_)),
+  );
+
+  format!(
+    "{for_params}{}fn({inputs}){}",
+    header_to_string(&pointer.header),
+    output_to_string(&pointer.sig)
+  )
+}
+
+fn header_to_string(header: &FunctionHeader) -> String {
+  let mut out = String::new();
+  if header.is_const {
+    out.push_str("const ");
+  }
+  if header.is_async {
+    out.push_str("async ");
+  }
+  if header.is_unsafe {
+    out.push_str("unsafe ");
+  }
+  let abi = match &header.abi {
+    Abi::Rust => None,
+    Abi::C { unwind } => Some(("C", *unwind)),
+    Abi::Cdecl { unwind } => Some(("cdecl", *unwind)),
+    Abi::Stdcall { unwind } => Some(("stdcall", *unwind)),
+    Abi::Fastcall { unwind } => Some(("fastcall", *unwind)),
+    Abi::Aapcs { unwind } => Some(("aapcs", *unwind)),
+    Abi::Win64 { unwind } => Some(("win64", *unwind)),
+    Abi::SysV64 { unwind } => Some(("sysv64", *unwind)),
+    Abi::System { unwind } => Some(("system", *unwind)),
+    Abi::Other(abi) => Some((abi.as_str(), false)),
+  };
+  if let Some((abi, unwind)) = abi {
+    let unwind = if unwind { "-unwind" } else { "" };
+    out.push_str(&format!("extern \"{abi}{unwind}\" "));
+  }
+  out
+}
+
+fn inputs_to_string(sig: &FunctionSignature) -> String {
+  let mut inputs = sig
+    .inputs
+    .iter()
+    .map(|(name, type_)| match (name.as_str(), type_) {
+      ("self", Type::Generic(self_type)) if self_type == "Self" => "self".to_string(),
+      ("self", Type::BorrowedRef { type_: inner, .. })
+        if matches!(inner.as_ref(), Type::Generic(self_type) if self_type == "Self") =>
+      {
+        // `&self`, `&mut self` and `&'a self`
+        type_to_string(type_).replacen("Self", "self", 1)
+      }
+      (name, type_) => format!("{name}: {}", type_to_string(type_)),
+    })
+    .collect::<Vec<_>>();
+
+  if sig.is_c_variadic {
+    inputs.push("...".to_string());
+  }
+  inputs.join(", ")
+}
+
+fn output_to_string(sig: &FunctionSignature) -> String {
+  match &sig.output {
+    Some(output) => format!(" -> {}", type_to_string(output)),
+    None => String::new(),
+  }
+}
+
+fn generic_param_to_string(param: &GenericParamDef) -> String {
+  match &param.kind {
+    GenericParamDefKind::Lifetime { outlives } => {
+      if outlives.is_empty() {
+        param.name.clone()
+      } else {
+        format!("{}: {}", param.name, outlives.join(" + "))
+      }
+    }
+    GenericParamDefKind::Type {
+      bounds, default, ..
+    } => {
+      let mut out = param.name.clone();
+      if !bounds.is_empty() {
+        out.push_str(&format!(": {}", bounds_to_string(bounds)));
+      }
+      if let Some(default) = default {
+        out.push_str(&format!(" = {}", type_to_string(default)));
+      }
+      out
+    }
+    GenericParamDefKind::Const { type_, default } => {
+      let mut out = format!("const {}: {}", param.name, type_to_string(type_));
+      if let Some(default) = default {
+        out.push_str(&format!(" = {default}"));
+      }
+      out
+    }
+  }
+}
+
+fn where_predicate_to_string(predicate: &WherePredicate) -> String {
+  match predicate {
+    WherePredicate::BoundPredicate {
+      type_,
+      bounds,
+      generic_params,
+    } => {
+      let for_params = if generic_params.is_empty() {
+        String::new()
+      } else {
+        format!(
+          "for<{}> ",
+          join(generic_params.iter().map(generic_param_to_string))
+        )
+      };
+      format!(
+        "{for_params}{}: {}",
+        type_to_string(type_),
+        bounds_to_string(bounds)
+      )
+    }
+    WherePredicate::LifetimePredicate { lifetime, outlives } => {
+      format!("{lifetime}: {}", outlives.join(" + "))
+    }
+    WherePredicate::EqPredicate { lhs, rhs } => {
+      format!("{} = {}", type_to_string(lhs), term_to_string(rhs))
+    }
+  }
+}
+
+fn bound_to_string(bound: &GenericBound) -> String {
+  match bound {
+    GenericBound::TraitBound {
+      trait_,
+      generic_params,
+      modifier,
+    } => {
+      let for_params = if generic_params.is_empty() {
+        String::new()
+      } else {
+        format!(
+          "for<{}> ",
+          join(generic_params.iter().map(generic_param_to_string))
+        )
+      };
+      let modifier = match modifier {
+        TraitBoundModifier::None => "",
+        TraitBoundModifier::Maybe => "?",
+        TraitBoundModifier::MaybeConst => "~const ",
+      };
+      format!("{for_params}{modifier}{}", path_to_string(trait_))
+    }
+    GenericBound::Outlives(lifetime) => lifetime.clone(),
+    GenericBound::Use(args) => format!(
+      "use<{}>",
+      join(args.iter().map(|arg| match arg {
+        PreciseCapturingArg::Lifetime(name)
+        | PreciseCapturingArg::Param(name) => {
+          name.clone()
+        }
+      }))
+    ),
+  }
+}
+
+fn poly_trait_to_string(poly_trait: &PolyTrait) -> String {
+  if poly_trait.generic_params.is_empty() {
+    path_to_string(&poly_trait.trait_)
+  } else {
+    format!(
+      "for<{}> {}",
+      join(
+        poly_trait
+          .generic_params
+          .iter()
+          .map(generic_param_to_string)
+      ),
+      path_to_string(&poly_trait.trait_)
+    )
+  }
+}
+
+fn generic_args_to_string(args: &GenericArgs) -> String {
+  match args {
+    GenericArgs::AngleBracketed { args, constraints } => {
+      let parts = args
+        .iter()
+        .map(generic_arg_to_string)
+        .chain(constraints.iter().map(constraint_to_string))
+        .collect::<Vec<_>>();
+      if parts.is_empty() {
+        String::new()
+      } else {
+        format!("<{}>", parts.join(", "))
+      }
+    }
+    GenericArgs::Parenthesized { inputs, output } => {
+      let inputs = join(inputs.iter().map(type_to_string));
+      match output {
+        Some(output) => format!("({inputs}) -> {}", type_to_string(output)),
+        None => format!("({inputs})"),
+      }
+    }
+    GenericArgs::ReturnTypeNotation => "(..)".to_string(),
+  }
+}
+
+fn generic_arg_to_string(arg: &GenericArg) -> String {
+  match arg {
+    GenericArg::Lifetime(lifetime) => lifetime.clone(),
+    GenericArg::Type(type_) => type_to_string(type_),
+    GenericArg::Const(constant) => constant.expr.clone(),
+    GenericArg::Infer => "_".to_string(),
+  }
+}
+
+fn constraint_to_string(constraint: &AssocItemConstraint) -> String {
+  let args = constraint
+    .args
+    .as_deref()
+    .map(generic_args_to_string)
+    .unwrap_or_default();
+  match &constraint.binding {
+    AssocItemConstraintKind::Equality(term) => {
+      format!("{}{args} = {}", constraint.name, term_to_string(term))
+    }
+    AssocItemConstraintKind::Constraint(bounds) => {
+      format!("{}{args}: {}", constraint.name, bounds_to_string(bounds))
+    }
+  }
+}
+
+fn term_to_string(term: &Term) -> String {
+  match term {
+    Term::Type(type_) => type_to_string(type_),
+    Term::Constant(constant) => constant.expr.clone(),
+  }
+}
+
+/// Render the `{ ... }` body of a struct or union with named fields.
+fn fields_block(
+  fields: &[Id],
+  has_stripped_fields: bool,
+  generics: &Generics,
+  index: &HashMap<Id, Item>,
+) -> String {
+  let mut out = where_clause(generics);
+  let fields = fields
+    .iter()
+    .filter_map(|id| index.get(id))
+    .filter_map(|field| item_signature(field, index))
+    .collect::<Vec<_>>();
+
+  if fields.is_empty() && !has_stripped_fields {
+    out.push_str(" {}");
+    return out;
+  }
+
+  out.push_str(if generics.where_predicates.is_empty() {
+    " {\n"
+  } else {
+    "\n{\n"
+  });
+  for field in fields {
+    out.push_str(&format!("{INDENT}{field},\n"));
+  }
+  if has_stripped_fields {
+    out.push_str(&format!("{INDENT}/* private fields */\n"));
+  }
+  out.push('}');
+  out
+}
+
+/// Render the fields of a tuple struct or tuple variant.
+///
+/// Stripped (private) fields are shown as `_`.
+fn tuple_fields(
+  fields: &[Option<Id>],
+  index: &HashMap<Id, Item>,
+  with_visibility: bool,
+) -> String {
+  join(fields.iter().map(|field| {
+    match field.as_ref().and_then(|id| index.get(id)) {
+      Some(Item {
+        inner: ItemEnum::StructField(type_),
+        visibility: field_vis,
+        ..
+      }) => {
+        let vis = if with_visibility {
+          visibility(field_vis)
+        } else {
+          String::new()
+        };
+        format!("{vis}{}", type_to_string(type_))
+      }
+      _ => "_".to_string(),
+    }
+  }))
+}
+
+/// Render a single enum variant, e.g. `Number { value: i64 }`.
+fn variant_to_string(item: &Item, index: &HashMap<Id, Item>) -> Option<String> {
+  let ItemEnum::Variant(variant) = &item.inner else {
+    return None;
+  };
+  let name = item.name.as_deref().unwrap_or("_");
+
+  let mut out = match &variant.kind {
+    VariantKind::Plain => name.to_string(),
+    VariantKind::Tuple(fields) => {
+      format!("{name}({})", tuple_fields(fields, index, false))
+    }
+    VariantKind::Struct {
+      fields,
+      has_stripped_fields,
+    } => {
+      let mut parts = fields
+        .iter()
+        .filter_map(|id| index.get(id))
+        .filter_map(|field| match &field.inner {
+          ItemEnum::StructField(type_) => Some(format!(
+            "{}: {}",
+            field.name.as_deref().unwrap_or("_"),
+            type_to_string(type_)
+          )),
+          _ => None,
+        })
+        .collect::<Vec<_>>();
+      if *has_stripped_fields {
+        parts.push("..".to_string());
+      }
+      format!("{name} {{ {} }}", parts.join(", "))
+    }
+  };
+
+  if let Some(discriminant) = &variant.discriminant {
+    out.push_str(&format!(" = {}", discriminant.expr));
+  }
+  Some(out)
+}
+
+fn indent(text: &str) -> String {
+  text
+    .lines()
+    .map(|line| format!("{INDENT}{line}"))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn join(parts: impl Iterator<Item = String>) -> String {
+  parts.collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::fixture_crate;
+
+  fn signature_of(name: &str) -> String {
+    let index = fixture_crate().index();
+    let item = index
+      .values()
+      .find(|item| {
+        item.name.as_deref() == Some(name)
+          && !matches!(item.inner, ItemEnum::Use(_))
+      })
+      .unwrap();
+    item_signature(item, &index).unwrap()
+  }
+
+  #[test]
+  fn test_function_signatures() {
+    assert_eq!(
+      signature_of("parse_async"),
+      "pub async fn parse_async(input: &str, limit: Option<usize>) -> Result<Vec<Token>>"
+    );
+    assert_eq!(
+      signature_of("count"),
+      "pub fn count<'a, I>(input: I) -> usize\nwhere\n    I: IntoIterator<Item = &'a str>,"
+    );
+    assert_eq!(signature_of("position"), "pub fn position(&self) -> usize");
+  }
+
+  #[test]
+  fn test_struct_and_enum_signatures() {
+    assert_eq!(
+      signature_of("Parser"),
+      "pub struct Parser<'a> {\n    pub input: &'a str,\n    pub keep_empty: bool,\n    /* private fields */\n}"
+    );
+    assert_eq!(
+      signature_of("Token"),
+      "pub enum Token {\n    Ident(String),\n    Number { value: i64, signed: bool },\n    Eof,\n}"
+    );
+
+    // A `where` clause ends a tuple struct without a trailing separator
+    let index = fixture_crate().index();
+    let mut tuple = index
+      .values()
+      .find(|item| item.name.as_deref() == Some("count"))
+      .unwrap()
+      .clone();
+    let ItemEnum::Function(count) = &tuple.inner else {
+      unreachable!()
+    };
+    tuple.name = Some("Counted".to_string());
+    tuple.inner = ItemEnum::Struct(rustdoc_types::Struct {
+      kind: StructKind::Tuple(vec![None]),
+      generics: count.generics.clone(),
+      impls: Vec::new(),
+    });
+    assert_eq!(
+      item_signature(&tuple, &index).unwrap(),
+      "pub struct Counted<'a, I>(_)\nwhere\n    I: IntoIterator<Item = &'a str>;"
+    );
+  }
+
+  #[test]
+  fn test_trait_and_alias_signatures() {
+    assert_eq!(
+      signature_of("Visitor"),
+      "pub trait Visitor: Clone {\n    type Output;\n    const LIMIT: usize = 16;\n    fn visit(&mut self, token: &Token) -> Option<Self::Output>;\n    fn visit_all(&mut self, tokens: &[Token]) -> Vec<Self::Output> { ... }\n}"
+    );
+    assert_eq!(
+      signature_of("Result"),
+      "pub type Result<T> = std::result::Result<T, error::Error>;"
+    );
+    assert_eq!(
+      signature_of("tokens"),
+      "macro_rules! tokens {\n    () => { ... };\n    ($($word:expr),+ $(,)?) => { ... };\n}"
+    );
+    let index = fixture_crate().index();
+    let mut derive = index.values().next().unwrap().clone();
+    derive.name = Some("Token".to_string());
+    derive.inner = ItemEnum::ProcMacro(rustdoc_types::ProcMacro {
+      kind: MacroKind::Derive,
+      helpers: vec!["token".to_string()],
+    });
+    assert_eq!(
+      item_signature(&derive, &index).unwrap(),
+      "#[derive(Token)]\n{\n    // Attributes available to this derive:\n    #[token]\n}"
+    );
+    assert_eq!(
+      signature_of("MAX_TOKENS"),
+      "pub const MAX_TOKENS: usize = 1024;"
+    );
+  }
+
+  #[test]
+  fn test_trait_member_where_clauses() {
+    // Members borrowing the generics and `where` clause of `count`
+    let mut index = fixture_crate().index();
+    let count = index
+      .values()
+      .find(|item| item.name.as_deref() == Some("count"))
+      .unwrap()
+      .clone();
+    let ItemEnum::Function(function) = &count.inner else {
+      unreachable!()
+    };
+    let mut method = count.clone();
+    method.id = Id(u32::MAX - 1);
+    method.visibility = Visibility::Default;
+    method.inner = ItemEnum::Function(rustdoc_types::Function {
+      has_body: false,
+      ..function.clone()
+    });
+    let mut assoc_type = method.clone();
+    assoc_type.id = Id(u32::MAX - 2);
+    assoc_type.name = Some("Iter".to_string());
+    assoc_type.inner = ItemEnum::AssocType {
+      generics: function.generics.clone(),
+      bounds: Vec::new(),
+      type_: Some(Type::Primitive("usize".to_string())),
+    };
+    let mut trait_ = method.clone();
+    trait_.id = Id(u32::MAX);
+    trait_.name = Some("Counter".to_string());
+    trait_.visibility = Visibility::Public;
+    trait_.inner = ItemEnum::Trait(rustdoc_types::Trait {
+      is_auto: false,
+      is_unsafe: false,
+      is_dyn_compatible: false,
+      items: vec![method.id, assoc_type.id],
+      generics: Generics {
+        params: Vec::new(),
+        where_predicates: Vec::new(),
+      },
+      bounds: Vec::new(),
+      implementations: Vec::new(),
+    });
+    for item in [&method, &assoc_type] {
+      index.insert(item.id, item.clone());
+    }
+
+    assert_eq!(
+      item_signature(&trait_, &index).unwrap(),
+      "pub trait Counter {\n    fn count<'a, I>(input: I) -> usize\n    where\n        I: IntoIterator<Item = &'a str>;\n    type Iter<'a, I>\n    where\n        I: IntoIterator<Item = &'a str> = usize;\n}"
+    );
+  }
+}
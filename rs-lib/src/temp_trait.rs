@@ -12,6 +12,25 @@
 //! - A unified `CommonCrates` trait for accessing crate data
 //! - Internal `Item` and `Crate` structures for compatibility
 //! - Conversion implementations between different format versions
+//! - A registry of adapters, one per range of supported `format_version`s,
+//!   each reading a rustdoc JSON document into a `CommonCrates`
+//!
+//! ## Supported Format Versions
+//!
+//! | Versions | Adapter | Read as |
+//! |----------|---------|---------|
+//! | 41..=43  | `v41`   | internal `Crate`, without `Crate::target` |
+//! | 44..=51  | `v44`   | internal `Crate`, attributes in source form |
+//! | 52..=53  | `v52`   | internal `Crate`, `#[must_use]` converted |
+//! | 54..=55  | `v54`   | `rustdoc_types::Crate` |
+//! | 56..=57  | `v56`   | `rustdoc_types::Crate`, cfg traces converted |
+//!
+//! Item kinds are read with the `rustdoc_types` 0.55 `ItemEnum` for every
+//! version: the older schemas only lack `GenericArgs::ReturnTypeNotation`
+//! (before 42) and write `AssocItemConstraint::args` and
+//! `Type::QualifiedPath::args` as required values (before 51), both of which
+//! it accepts. Each range is tested against the output of a toolchain that
+//! emits it, see `tests/fixtures/generate.sh`.
 
 use rustdoc_types::{
   Deprecation, ExternalCrate, Id, ItemEnum, ItemSummary, Span, Target,
@@ -19,6 +38,54 @@ use rustdoc_types::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use crate::error::{Error, Result};
+
+mod v41;
+mod v44;
+mod v52;
+mod v54;
+mod v56;
+
+/// Reads the rustdoc JSON documents of a range of format versions.
+pub struct FormatAdapter {
+  /// The format versions read by this adapter
+  pub versions: RangeInclusive<u32>,
+  /// Deserialize a document and convert it into the common item model
+  pub parse: fn(&[u8]) -> Result<Box<dyn CommonCrates>>,
+}
+
+/// The supported format versions, oldest first. Ranges are contiguous.
+pub const FORMAT_ADAPTERS: [FormatAdapter; 5] = [
+  FormatAdapter {
+    versions: v41::VERSIONS,
+    parse: v41::parse,
+  },
+  FormatAdapter {
+    versions: v44::VERSIONS,
+    parse: v44::parse,
+  },
+  FormatAdapter {
+    versions: v52::VERSIONS,
+    parse: v52::parse,
+  },
+  FormatAdapter {
+    versions: v54::VERSIONS,
+    parse: v54::parse,
+  },
+  FormatAdapter {
+    versions: v56::VERSIONS,
+    parse: v56::parse,
+  },
+];
+
+/// All supported format versions, e.g. `41..=57`.
+pub fn supported_format_versions() -> RangeInclusive<u32> {
+  let first = &FORMAT_ADAPTERS[0];
+  let last = &FORMAT_ADAPTERS[FORMAT_ADAPTERS.len() - 1];
+  *first.versions.start()..=*last.versions.end()
+}
 
 /// Represents any documented item in a Rust crate.
 ///
@@ -46,10 +113,9 @@ pub struct Item {
   pub links: HashMap<String, Id>,
   /// Rust attributes applied to this item (excluding #[deprecated])
   ///
-  /// Attributes are normalized to their canonical form:
-  /// - `#[non_exhaustive]`, `#[must_use]` appear as-is
-  /// - `#[repr(C)]` may have reordered parameters
-  /// - Multiple similar attributes may be combined
+  /// Attributes are kept in their source form, e.g. `#[must_use]` or
+  /// `#[doc(cfg(feature = "json"))]`; see `attrs::parse_attrs` for the
+  /// typed form.
   pub attrs: Vec<String>,
   /// Deprecation information if the item is deprecated
   pub deprecation: Option<Deprecation>,
@@ -69,16 +135,61 @@ pub trait CommonCrates: Send + Sync {
   /// Returns the ID of the root module of this crate
   fn root_id(&self) -> Id;
 
-  /// Returns the version string of this crate
+  /// Returns the version of this crate, if the documentation records it
   ///
-  /// If no version is available, returns "latest" as a fallback
-  fn crate_version(&self) -> String;
+  /// Older rustdoc JSON may not; callers resolve the version from another
+  /// source, such as the manifest or the crates.io index
+  fn crate_version(&self) -> Option<String>;
+
+  /// Returns the name of the root module, i.e. the crate name
+  ///
+  /// Unlike `index`, this does not build the item map
+  fn root_name(&self) -> Option<String>;
 
   /// Returns a mapping of all documented items in this crate
   ///
   /// The HashMap maps item IDs to their corresponding `Item` structures,
   /// providing access to all documentation content and metadata
   fn index(&self) -> HashMap<Id, Item>;
+
+  /// Returns a mapping of item IDs to their fully qualified paths and kinds
+  ///
+  /// Covers items of this crate as well as external items referenced by it,
+  /// and is used to build canonical titles such as `clap::builder::Arg`
+  fn paths(&self) -> HashMap<Id, ItemSummary>;
+
+  /// Returns the crates referenced by this crate, keyed by the `crate_id`
+  /// used in `paths`
+  ///
+  /// Used to link to the documentation of items from dependencies
+  fn external_crates(&self) -> HashMap<u32, ExternalCrate>;
+}
+
+/// Boxed crates, as returned by `parse_crate`, are crates too.
+impl<T: CommonCrates + ?Sized> CommonCrates for Box<T> {
+  fn root_id(&self) -> Id {
+    (**self).root_id()
+  }
+
+  fn crate_version(&self) -> Option<String> {
+    (**self).crate_version()
+  }
+
+  fn root_name(&self) -> Option<String> {
+    (**self).root_name()
+  }
+
+  fn index(&self) -> HashMap<Id, Item> {
+    (**self).index()
+  }
+
+  fn paths(&self) -> HashMap<Id, ItemSummary> {
+    (**self).paths()
+  }
+
+  fn external_crates(&self) -> HashMap<u32, ExternalCrate> {
+    (**self).external_crates()
+  }
 }
 
 /// Implementation of `CommonCrates` for the standard `rustdoc_types::Crate`.
@@ -90,8 +201,12 @@ impl CommonCrates for rustdoc_types::Crate {
     self.root
   }
 
-  fn crate_version(&self) -> String {
-    self.crate_version.clone().unwrap_or("latest".to_string())
+  fn crate_version(&self) -> Option<String> {
+    self.crate_version.clone()
+  }
+
+  fn root_name(&self) -> Option<String> {
+    self.index.get(&self.root)?.name.clone()
   }
 
   fn index(&self) -> HashMap<Id, Item> {
@@ -108,13 +223,8 @@ impl CommonCrates for rustdoc_types::Crate {
         visibility: item.visibility.clone(),
         docs: item.docs.clone(),
         links: item.links.clone(),
-        // Convert attributes to JSON strings for consistent handling
-        // Use try_collect to handle potential serialization errors gracefully
-        attrs: item
-          .attrs
-          .iter()
-          .filter_map(|attr| serde_json::to_string(attr).ok())
-          .collect(),
+        // Convert attributes to their source form, parsed by `attrs`
+        attrs: item.attrs.iter().map(crate::attrs::to_source).collect(),
         deprecation: item.deprecation.clone(),
         inner: item.inner.clone(),
       };
@@ -123,6 +233,14 @@ impl CommonCrates for rustdoc_types::Crate {
 
     hash_map
   }
+
+  fn paths(&self) -> HashMap<Id, ItemSummary> {
+    self.paths.clone()
+  }
+
+  fn external_crates(&self) -> HashMap<u32, ExternalCrate> {
+    self.external_crates.clone()
+  }
 }
 
 /// Implementation of `CommonCrates` for our internal `Crate` structure.
@@ -134,14 +252,26 @@ impl CommonCrates for Crate {
     self.root
   }
 
-  fn crate_version(&self) -> String {
-    self.crate_version.clone().unwrap_or("latest".to_string())
+  fn crate_version(&self) -> Option<String> {
+    self.crate_version.clone()
+  }
+
+  fn root_name(&self) -> Option<String> {
+    self.index.get(&self.root)?.name.clone()
   }
 
   fn index(&self) -> HashMap<Id, Item> {
     // Direct clone since the format already matches our internal representation
     self.index.clone()
   }
+
+  fn paths(&self) -> HashMap<Id, ItemSummary> {
+    self.paths.clone()
+  }
+
+  fn external_crates(&self) -> HashMap<u32, ExternalCrate> {
+    self.external_crates.clone()
+  }
 }
 
 /// Internal representation of a Rust crate's documentation.
@@ -177,3 +307,155 @@ pub struct Crate {
   /// Used to handle changes in the JSON schema over time
   pub format_version: u32,
 }
+
+/// Read the `format_version` of a rustdoc JSON document.
+///
+/// Only the `format_version` field is deserialized; the rest of the document
+/// is skipped over without building any values.
+///
+/// # Errors
+///
+/// * `Error::Json` - If the data is not a JSON object
+/// * `Error::Config` - If the document has no `format_version`
+pub fn format_version(json: &[u8]) -> Result<u32> {
+  #[derive(Deserialize)]
+  struct Header {
+    format_version: Option<u32>,
+  }
+
+  serde_json::from_slice::<Header>(json)?
+    .format_version
+    .ok_or_else(|| {
+      Error::Config("rustdoc JSON has no format_version".to_string())
+    })
+}
+
+/// Read the `crate_version` of a rustdoc JSON document, skipping the rest of
+/// the document as `format_version` does.
+///
+/// # Errors
+///
+/// * `Error::Json` - If the data is not a JSON object
+pub fn crate_version(json: &[u8]) -> Result<Option<String>> {
+  #[derive(Deserialize)]
+  struct Header {
+    crate_version: Option<String>,
+  }
+
+  Ok(serde_json::from_slice::<Header>(json)?.crate_version)
+}
+
+/// Parse a rustdoc JSON document with the adapter for its `format_version`.
+///
+/// # Arguments
+///
+/// * `json` - The rustdoc JSON document, decompressed
+///
+/// # Returns
+///
+/// * `Result<Box<dyn CommonCrates>>` - The crate, as read by the adapter in
+///   `FORMAT_ADAPTERS` whose range holds its format version
+///
+/// # Errors
+///
+/// * `Error::Config` - If the format version is not supported
+/// * `Error::Json` - If the document does not match its format version
+pub fn parse_crate(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  let version = format_version(json)?;
+  match FORMAT_ADAPTERS
+    .iter()
+    .find(|adapter| adapter.versions.contains(&version))
+  {
+    Some(adapter) => (adapter.parse)(json),
+    None => {
+      let supported = supported_format_versions();
+      Err(Error::Config(format!(
+        "unsupported format_version {version} (supported: {}..={})",
+        supported.start(),
+        supported.end()
+      )))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::attrs::{self, ItemAttributes};
+
+  const FIXTURES: [(u32, &str); 5] = [
+    (43, include_str!("../tests/fixtures/demo.v43.json")),
+    (45, include_str!("../tests/fixtures/demo.v45.json")),
+    (53, include_str!("../tests/fixtures/demo.v53.json")),
+    (55, include_str!("../tests/fixtures/demo.json")),
+    (57, include_str!("../tests/fixtures/demo.v57.json")),
+  ];
+
+  #[test]
+  fn test_parse_crate_fixtures() {
+    // One fixture per adapter
+    for (adapter, (version, json)) in FORMAT_ADAPTERS.iter().zip(FIXTURES) {
+      assert!(adapter.versions.contains(&version));
+      assert_eq!(format_version(json.as_bytes()).unwrap(), version);
+      assert_eq!(
+        crate_version(json.as_bytes()).unwrap().as_deref(),
+        Some("0.3.1")
+      );
+
+      let krate = parse_crate(json.as_bytes()).unwrap();
+      assert_eq!(
+        krate.crate_version().as_deref(),
+        Some("0.3.1"),
+        "format {version}"
+      );
+      assert_eq!(
+        krate.root_name().as_deref(),
+        Some("demo"),
+        "format {version}"
+      );
+      let index = krate.index();
+      let item = |name: &str| {
+        index
+          .values()
+          .find(|item| item.name.as_deref() == Some(name))
+          .unwrap()
+      };
+      assert_eq!(
+        item(&index[&krate.root_id()].name.clone().unwrap()).id,
+        krate.root_id()
+      );
+      assert!(matches!(item("Parser").inner, ItemEnum::Struct(_)));
+
+      // Attributes read the same whatever their form in the document
+      let attributes = |name: &str| {
+        ItemAttributes::from_attrs(&attrs::parse_attrs(&item(name).attrs))
+      };
+      assert_eq!(
+        attributes("to_json").cfg,
+        vec!["feature = \"json\""],
+        "format {version}"
+      );
+      assert!(attributes("count").must_use, "format {version}");
+      assert!(attributes("Token").non_exhaustive, "format {version}");
+    }
+  }
+
+  #[test]
+  fn test_parse_crate_unsupported() {
+    assert_eq!(supported_format_versions(), 41..=57);
+    let json = FIXTURES[0]
+      .1
+      .replace("\"format_version\":43", "\"format_version\":40");
+    let Err(err) = parse_crate(json.as_bytes()) else {
+      panic!("format_version 40 is not supported");
+    };
+    assert_eq!(
+      err.to_string(),
+      "configuration error: unsupported format_version 40 (supported: 41..=57)"
+    );
+    assert!(matches!(
+      parse_crate(br#"{"root": 0}"#),
+      Err(Error::Config(_))
+    ));
+  }
+}
@@ -0,0 +1,52 @@
+//! # Format Versions 41 to 43
+//!
+//! These versions predate `Crate::target` and keep attributes in their source
+//! form. They are read into the internal `Crate` with an empty target.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use rustdoc_types::{ExternalCrate, Id, ItemSummary, Target};
+use serde::Deserialize;
+
+use super::{CommonCrates, Item};
+use crate::error::Result;
+
+/// The format versions read by this adapter
+pub const VERSIONS: RangeInclusive<u32> = 41..=43;
+
+/// A crate of these versions, without a target
+#[derive(Deserialize)]
+struct Crate {
+  root: Id,
+  crate_version: Option<String>,
+  includes_private: bool,
+  index: HashMap<Id, Item>,
+  paths: HashMap<Id, ItemSummary>,
+  external_crates: HashMap<u32, ExternalCrate>,
+  format_version: u32,
+}
+
+impl From<Crate> for super::Crate {
+  fn from(krate: Crate) -> Self {
+    super::Crate {
+      root: krate.root,
+      crate_version: krate.crate_version,
+      includes_private: krate.includes_private,
+      index: krate.index,
+      paths: krate.paths,
+      external_crates: krate.external_crates,
+      target: Target {
+        triple: String::new(),
+        target_features: Vec::new(),
+      },
+      format_version: krate.format_version,
+    }
+  }
+}
+
+/// Read a rustdoc JSON document of these versions.
+pub fn parse(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  let krate: super::Crate = serde_json::from_slice::<Crate>(json)?.into();
+  Ok(Box::new(krate))
+}
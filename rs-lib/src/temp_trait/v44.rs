@@ -0,0 +1,20 @@
+//! # Format Versions 44 to 51
+//!
+//! From the introduction of `Crate::target` to the last version writing
+//! `#[must_use]` in its source form, documents deserialize directly into the
+//! internal `Crate`. Up to format 50, rustdoc writes empty generic args as
+//! `{"angle_bracketed": {"args": [], "constraints": []}}` rather than `null`,
+//! which renders the same.
+
+use std::ops::RangeInclusive;
+
+use super::{CommonCrates, Crate};
+use crate::error::Result;
+
+/// The format versions read by this adapter
+pub const VERSIONS: RangeInclusive<u32> = 44..=51;
+
+/// Read a rustdoc JSON document of these versions.
+pub fn parse(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  Ok(Box::new(serde_json::from_slice::<Crate>(json)?))
+}
@@ -0,0 +1,77 @@
+//! # Format Versions 52 and 53
+//!
+//! These versions share the schema of 44 to 51, but rustdoc writes
+//! `#[must_use]` as parsed by the compiler, e.g.
+//! `#[attr = MustUse {reason: "text"}]`. The attribute is turned back into
+//! its source form so that `attrs` can read it.
+
+use std::ops::RangeInclusive;
+
+use super::{CommonCrates, Crate};
+use crate::error::Result;
+
+/// The format versions read by this adapter
+pub const VERSIONS: RangeInclusive<u32> = 52..=53;
+
+/// Read a rustdoc JSON document of these versions.
+pub fn parse(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  let mut krate = serde_json::from_slice::<Crate>(json)?;
+  for item in krate.index.values_mut() {
+    for attr in &mut item.attrs {
+      if let Some(source) = must_use_source(attr) {
+        *attr = source;
+      }
+    }
+  }
+  Ok(Box::new(krate))
+}
+
+/// The source form of a `#[attr = MustUse ...]` attribute, e.g.
+/// `#[must_use = "text"]`, or `None` for other attributes.
+fn must_use_source(attr: &str) -> Option<String> {
+  let rest = attr.strip_prefix("#[attr = MustUse")?.strip_suffix(']')?;
+  let rest = rest.trim();
+  if rest.is_empty() {
+    return Some("#[must_use]".to_string());
+  }
+  let reason = rest
+    .strip_prefix('{')?
+    .strip_suffix('}')?
+    .trim()
+    .strip_prefix("reason:")?
+    .trim();
+  let is_literal =
+    reason.len() >= 2 && reason.starts_with('"') && reason.ends_with('"');
+  is_literal.then(|| format!("#[must_use = {reason}]"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_must_use_source() {
+    assert_eq!(
+      must_use_source("#[attr = MustUse]").as_deref(),
+      Some("#[must_use]")
+    );
+    assert_eq!(
+      must_use_source(r#"#[attr = MustUse {reason: "iterators are lazy"}]"#)
+        .as_deref(),
+      Some(r#"#[must_use = "iterators are lazy"]"#)
+    );
+    assert_eq!(must_use_source("#[attr = Inline(Hint)]"), None);
+    assert_eq!(must_use_source("#[must_use]"), None);
+  }
+
+  #[test]
+  fn test_parse_converts_must_use() {
+    let json = include_str!("../../tests/fixtures/demo.v53.json");
+    let index = parse(json.as_bytes()).unwrap().index();
+    let count = index
+      .values()
+      .find(|item| item.name.as_deref() == Some("count"))
+      .unwrap();
+    assert_eq!(count.attrs, ["#[must_use]"]);
+  }
+}
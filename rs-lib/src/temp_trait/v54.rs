@@ -0,0 +1,20 @@
+//! # Format Versions 54 and 55
+//!
+//! The versions described by `rustdoc_types` 0.55, whose attributes are
+//! structured (`rustdoc_types::Attribute`). Format 55 only added
+//! `Attribute::MacroUse`, which 54 reports as `Attribute::Other`.
+
+use std::ops::RangeInclusive;
+
+use super::CommonCrates;
+use crate::error::Result;
+
+/// The format versions read by this adapter
+pub const VERSIONS: RangeInclusive<u32> = 54..=rustdoc_types::FORMAT_VERSION;
+
+/// Read a rustdoc JSON document of these versions.
+pub fn parse(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  Ok(Box::new(serde_json::from_slice::<rustdoc_types::Crate>(
+    json,
+  )?))
+}
@@ -0,0 +1,194 @@
+//! # Format Versions 56 and 57
+//!
+//! These versions only add `ExternalCrate::path` to the schema of
+//! `rustdoc_types` 0.55, but rustdoc now reports `#[cfg]` attributes as a
+//! trace of the parsed predicates, e.g.
+//! `#[attr = CfgTrace([NameValue { name: "feature", value: Some("json"), span: .. }])]`.
+//! The traces are turned back into `#[cfg(...)]` attributes so that `attrs`
+//! can read them.
+
+use std::ops::RangeInclusive;
+
+use rustdoc_types::Attribute;
+
+use super::CommonCrates;
+use crate::error::Result;
+
+/// The format versions read by this adapter
+pub const VERSIONS: RangeInclusive<u32> = 56..=57;
+
+/// Read a rustdoc JSON document of these versions.
+pub fn parse(json: &[u8]) -> Result<Box<dyn CommonCrates>> {
+  let mut krate = serde_json::from_slice::<rustdoc_types::Crate>(json)?;
+  for item in krate.index.values_mut() {
+    item.attrs = std::mem::take(&mut item.attrs)
+      .into_iter()
+      .flat_map(|attr| match &attr {
+        Attribute::Other(source) => match traced_cfgs(source) {
+          Some(predicates) => predicates
+            .into_iter()
+            .map(|predicate| Attribute::Other(format!("#[cfg({predicate})]")))
+            .collect(),
+          None => vec![attr],
+        },
+        _ => vec![attr],
+      })
+      .collect();
+  }
+  Ok(Box::new(krate))
+}
+
+/// The cfg predicates of a `#[attr = CfgTrace([...])]` attribute, e.g.
+/// `feature = "json"`, one per `#[cfg]` attribute it traces.
+///
+/// Returns `None` for other attributes and for predicates this module does
+/// not know, which are then kept in their traced form.
+fn traced_cfgs(attr: &str) -> Option<Vec<String>> {
+  let list = attr.strip_prefix("#[attr = CfgTrace(")?;
+  let (predicates, rest) = cfg_list(list)?;
+  (rest.trim() == ")]").then_some(predicates)
+}
+
+/// Parse a bracketed list of traced predicates, returning the predicates and
+/// the input after the closing bracket.
+fn cfg_list(source: &str) -> Option<(Vec<String>, &str)> {
+  let mut rest = source.trim_start().strip_prefix('[')?;
+  let mut predicates = Vec::new();
+  loop {
+    rest = rest.trim_start();
+    if let Some(rest) = rest.strip_prefix(']') {
+      return Some((predicates, rest));
+    }
+    let (predicate, after) = cfg_entry(rest)?;
+    predicates.push(predicate);
+    rest = after.trim_start();
+    rest = rest.strip_prefix(',').unwrap_or(rest);
+  }
+}
+
+/// Parse a traced predicate, returning it in source form and the input after
+/// it. Spans are skipped.
+fn cfg_entry(source: &str) -> Option<(String, &str)> {
+  let source = source.trim_start();
+  if let Some(rest) = source.strip_prefix("NameValue {") {
+    let rest = rest.trim_start().strip_prefix("name:")?;
+    let (name, rest) = string_literal(rest)?;
+    let rest = rest
+      .trim_start()
+      .strip_prefix(',')?
+      .trim_start()
+      .strip_prefix("value:")?
+      .trim_start();
+    let (predicate, rest) = match rest.strip_prefix("Some(") {
+      Some(rest) => {
+        let (value, rest) = string_literal(rest)?;
+        (format!("{name} = \"{value}\""), rest.strip_prefix(')')?)
+      }
+      None => (name.to_string(), rest.strip_prefix("None")?),
+    };
+    return Some((predicate, close(rest, '}')?));
+  }
+  for (variant, operator) in [("All(", "all"), ("Any(", "any")] {
+    if let Some(rest) = source.strip_prefix(variant) {
+      let (predicates, rest) = cfg_list(rest)?;
+      let predicate = format!("{operator}({})", predicates.join(", "));
+      return Some((predicate, close(rest, ')')?));
+    }
+  }
+  if let Some(rest) = source.strip_prefix("Not(") {
+    let (predicate, rest) = cfg_entry(rest)?;
+    return Some((format!("not({predicate})"), close(rest, ')')?));
+  }
+  if let Some(rest) = source.strip_prefix("Bool(") {
+    let (value, rest) = rest.split_once(',')?;
+    return Some((value.trim().to_string(), close(rest, ')')?));
+  }
+  None
+}
+
+/// Parse a string literal in debug form, returning its escaped contents and
+/// the input after it.
+fn string_literal(source: &str) -> Option<(&str, &str)> {
+  let source = source.trim_start().strip_prefix('"')?;
+  let mut escaped = false;
+  for (i, c) in source.char_indices() {
+    match c {
+      '\\' if !escaped => escaped = true,
+      '"' if !escaped => return Some((&source[..i], &source[i + 1..])),
+      _ => escaped = false,
+    }
+  }
+  None
+}
+
+/// Skip to the unmatched `delimiter` closing the current group, returning
+/// the input after it.
+fn close(source: &str, delimiter: char) -> Option<&str> {
+  let mut depth = 0usize;
+  let mut rest = source;
+  while let Some(c) = rest.chars().next() {
+    if c == '"' {
+      rest = string_literal(rest)?.1;
+      continue;
+    }
+    rest = &rest[c.len_utf8()..];
+    match c {
+      '(' | '[' | '{' => depth += 1,
+      ')' | ']' | '}' if depth == 0 => return (c == delimiter).then_some(rest),
+      ')' | ']' | '}' => depth -= 1,
+      _ => {}
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_traced_cfgs() {
+    assert_eq!(
+      traced_cfgs(
+        r#"#[attr = CfgTrace([NameValue { name: "feature", value: Some("json"), span: src/lib.rs:57:7: 57:23 (#0) }])]"#
+      ),
+      Some(vec!["feature = \"json\"".to_string()])
+    );
+    assert_eq!(
+      traced_cfgs(
+        "#[attr = CfgTrace([All([NameValue { name: \"unix\", value: None, span: src/lib.rs:85:11: 85:15 (#0) }, \
+         Not(NameValue { name: \"windows\", value: None, span: src/lib.rs:85:39: 85:46 (#0) }, src/lib.rs:85:38: 85:47 (#0))], \
+         src/lib.rs:85:10: 85:48 (#0)),\nAny([Bool(true, src/lib.rs:92:7: 92:11 (#0))], src/lib.rs:89:7: 89:11 (#0))])]"
+      ),
+      Some(vec![
+        "all(unix, not(windows))".to_string(),
+        "any(true)".to_string()
+      ])
+    );
+    assert_eq!(traced_cfgs("#[attr = CfgAttrTrace]"), None);
+    assert_eq!(
+      traced_cfgs(
+        r#"#[attr = CfgTrace([Version("1.80", src/lib.rs:1:1: 1:2 (#0))])]"#
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn test_parse_converts_cfg_traces() {
+    let json = include_str!("../../tests/fixtures/demo.v57.json");
+    let index = parse(json.as_bytes()).unwrap().index();
+    let to_json = index
+      .values()
+      .find(|item| item.name.as_deref() == Some("to_json"))
+      .unwrap();
+    assert_eq!(
+      to_json.attrs,
+      [
+        "#[cfg(feature = \"json\")]",
+        "#[attr = CfgAttrTrace]",
+        "#[doc(cfg(feature = \"json\"))]"
+      ]
+    );
+  }
+}
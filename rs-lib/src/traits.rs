@@ -0,0 +1,179 @@
+//! # Trait Listing
+//!
+//! A trait page tells its reader what an implementation must provide. This
+//! module groups the associated items of a trait into required and provided
+//! ones, as on its rustdoc page, and collects its supertraits and the impls of
+//! the trait found in the crate.
+
+use std::collections::HashMap;
+use std::slice;
+
+use rustdoc_types::{Id, ItemEnum};
+
+use crate::signature;
+use crate::temp_trait::Item;
+
+/// Group the associated items of a trait by kind, separating the items an
+/// implementation must define from the ones that have a default.
+///
+/// # Arguments
+///
+/// * `item` - The trait
+/// * `index` - The crate index, from `CommonCrates::index`
+///
+/// # Returns
+///
+/// * `Vec<(&'static str, Vec<&Item>)>` - The non-empty groups with their
+///   rustdoc heading, e.g. `Required Methods`, in rustdoc order; empty for
+///   other kinds of items
+pub fn trait_item_groups<'a>(
+  item: &Item,
+  index: &'a HashMap<Id, Item>,
+) -> Vec<(&'static str, Vec<&'a Item>)> {
+  let ItemEnum::Trait(trait_) = &item.inner else {
+    return Vec::new();
+  };
+  let mut groups: Vec<(&'static str, Vec<&Item>)> = [
+    "Required Associated Types",
+    "Provided Associated Types",
+    "Required Associated Constants",
+    "Provided Associated Constants",
+    "Required Methods",
+    "Provided Methods",
+  ]
+  .into_iter()
+  .map(|heading| (heading, Vec::new()))
+  .collect();
+
+  for member in trait_.items.iter().filter_map(|id| index.get(id)) {
+    let group = match &member.inner {
+      ItemEnum::AssocType { type_, .. } => usize::from(type_.is_some()),
+      ItemEnum::AssocConst { value, .. } => 2 + usize::from(value.is_some()),
+      ItemEnum::Function(function) => 4 + usize::from(function.has_body),
+      _ => continue,
+    };
+    groups[group].1.push(member);
+  }
+
+  groups.retain(|(_, members)| !members.is_empty());
+  groups
+}
+
+/// List the supertraits of a trait, e.g. `Clone` or `Iterator<Item = u8>`.
+pub fn supertraits(item: &Item) -> Vec<String> {
+  match &item.inner {
+    ItemEnum::Trait(trait_) => trait_
+      .bounds
+      .iter()
+      .map(|bound| signature::bounds_to_string(slice::from_ref(bound)))
+      .collect(),
+    _ => Vec::new(),
+  }
+}
+
+/// List the impls of a trait found in the crate, e.g.
+/// `impl<'a> Visitor for Parser<'a>`.
+///
+/// # Arguments
+///
+/// * `item` - The trait
+/// * `index` - The crate index, from `CommonCrates::index`
+///
+/// # Returns
+///
+/// * `Vec<String>` - The impl headers, in declaration order
+pub fn implementors(item: &Item, index: &HashMap<Id, Item>) -> Vec<String> {
+  let ItemEnum::Trait(trait_) = &item.inner else {
+    return Vec::new();
+  };
+  trait_
+    .implementations
+    .iter()
+    .filter_map(|id| index.get(id))
+    .filter(|impl_| matches!(impl_.inner, ItemEnum::Impl(_)))
+    .filter_map(|impl_| signature::item_signature(impl_, index))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::options::ProcessOptions;
+  use crate::temp_trait::CommonCrates;
+  use crate::tests::{fixture_crate, session};
+  use crate::CrateDocs;
+
+  #[test]
+  fn test_visitor_trait() {
+    let index = fixture_crate().index();
+    let visitor = index
+      .values()
+      .find(|item| matches!(item.inner, ItemEnum::Trait(_)))
+      .unwrap();
+
+    let groups = trait_item_groups(visitor, &index)
+      .into_iter()
+      .map(|(heading, members)| {
+        let names = members
+          .into_iter()
+          .filter_map(|member| member.name.as_deref())
+          .collect::<Vec<_>>();
+        (heading, names)
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      groups,
+      vec![
+        ("Required Associated Types", vec!["Output"]),
+        ("Provided Associated Constants", vec!["LIMIT"]),
+        ("Required Methods", vec!["visit"]),
+        ("Provided Methods", vec!["visit_all"]),
+      ]
+    );
+
+    assert_eq!(supertraits(visitor), vec!["Clone"]);
+    assert_eq!(
+      implementors(visitor, &index),
+      vec!["impl<'a> Visitor for Parser<'a>"]
+    );
+  }
+
+  #[test]
+  fn test_process_docs_traits() {
+    // Undocumented trait items are still part of the trait's contract
+    let mut krate = fixture_crate();
+    for item in krate.index.values_mut() {
+      if item.name.as_deref() == Some("visit") {
+        item.docs = None;
+      }
+    }
+    let docs =
+      CrateDocs::process_docs("demo", krate, None, &ProcessOptions::default())
+        .unwrap();
+    let content = &session(&docs, "demo::parser::Visitor").1.content;
+
+    let headings = content
+      .lines()
+      .filter(|line| line.starts_with("# "))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      headings,
+      vec![
+        "# Supertraits",
+        "# Required Associated Types",
+        "# Provided Associated Constants",
+        "# Required Methods",
+        "# Provided Methods",
+        "# Implementors",
+      ]
+    );
+    assert!(content.contains(
+      "# Required Methods\n\n```rust\n\
+       fn visit(&mut self, token: &Token) -> Option<Self::Output>\n```\n\n\
+       # Provided Methods"
+    ));
+    assert!(content.ends_with(
+      "# Implementors\n\n```rust\nimpl<'a> Visitor for Parser<'a>\n```"
+    ));
+  }
+}
@@ -0,0 +1,282 @@
+//! # docs.rs URL Construction
+//!
+//! This module computes links to the pages rendered by docs.rs for a crate:
+//! item pages such as `https://docs.rs/clap/4.5.39/clap/builder/struct.Arg.html`
+//! and source views such as
+//! `https://docs.rs/crate/clap/4.5.39/source/src/builder/arg.rs#42`.
+//! Items of dependencies link to the documentation their crate is hosted at.
+
+use rustdoc_types::{ExternalCrate, ItemKind, ItemSummary, Span};
+
+/// Base URL of docs.rs
+const DOCS_RS_URL: &str = "https://docs.rs";
+
+/// Compute the docs.rs page of an item from its path summary.
+///
+/// Enum variants, which are rendered on the page of their enum, link to the
+/// matching anchor on that page.
+///
+/// # Arguments
+///
+/// * `lib_name` - The name of the crate as published on crates.io
+/// * `version` - The version of the crate
+/// * `summary` - The path and kind of the item, from `CommonCrates::paths`
+///
+/// # Returns
+///
+/// * `Option<String>` - The URL, or `None` for kinds without a docs.rs page
+///
+/// # Examples
+///
+/// ```
+/// use crates_llms_txt::urls::item_url;
+/// use rustdoc_types::{ItemKind, ItemSummary};
+///
+/// let summary = ItemSummary {
+///   crate_id: 0,
+///   path: vec!["clap".into(), "builder".into(), "Arg".into()],
+///   kind: ItemKind::Struct,
+/// };
+/// assert_eq!(
+///   item_url("clap", "4.5.39", &summary).unwrap(),
+///   "https://docs.rs/clap/4.5.39/clap/builder/struct.Arg.html"
+/// );
+/// ```
+pub fn item_url(
+  lib_name: &str,
+  version: &str,
+  summary: &ItemSummary,
+) -> Option<String> {
+  page_url(&format!("{DOCS_RS_URL}/{lib_name}/{version}"), summary)
+}
+
+/// Compute the documentation page of an item of another crate.
+///
+/// Pages are resolved against the `html_root_url` of the crate, e.g.
+/// `https://doc.rust-lang.org/1.90.0/` for the standard library, falling back
+/// to the latest release on docs.rs when the crate has none.
+///
+/// # Arguments
+///
+/// * `external` - The crate the item belongs to, from `Crate::external_crates`
+/// * `summary` - The path and kind of the item, from `CommonCrates::paths`
+///
+/// # Returns
+///
+/// * `Option<String>` - The URL, or `None` for kinds without a page
+pub fn external_item_url(
+  external: &ExternalCrate,
+  summary: &ItemSummary,
+) -> Option<String> {
+  let base = match &external.html_root_url {
+    Some(root) => root.trim_end_matches('/').to_string(),
+    None => format!("{DOCS_RS_URL}/{}/latest", external.name),
+  };
+  page_url(&base, summary)
+}
+
+/// Compute the page of an item relative to the documentation root of its
+/// crate.
+fn page_url(base: &str, summary: &ItemSummary) -> Option<String> {
+  if summary.kind == ItemKind::Module {
+    return Some(format!("{base}/{}/index.html", summary.path.join("/")));
+  }
+
+  let (name, parents) = summary.path.split_last()?;
+
+  match summary.kind {
+    ItemKind::Variant => {
+      let (enum_name, modules) = parents.split_last()?;
+      Some(format!(
+        "{base}/{}/enum.{enum_name}.html#variant.{name}",
+        modules.join("/")
+      ))
+    }
+    kind => {
+      let prefix = page_prefix(kind)?;
+      Some(format!("{base}/{}/{prefix}.{name}.html", parents.join("/")))
+    }
+  }
+}
+
+/// Compute the docs.rs source view of a span, anchored at its first line.
+///
+/// # Arguments
+///
+/// * `lib_name` - The name of the crate as published on crates.io
+/// * `version` - The version of the crate
+/// * `span` - The source location of the item
+///
+/// # Returns
+///
+/// * `String` - The URL of the source view
+pub fn source_url(lib_name: &str, version: &str, span: &Span) -> String {
+  let filename = span.filename.to_string_lossy().replace('\\', "/");
+  format!(
+    "{DOCS_RS_URL}/crate/{lib_name}/{version}/source/{filename}#{}",
+    span.begin.0
+  )
+}
+
+/// Compute the docs.rs landing page of a crate.
+pub fn crate_url(lib_name: &str, version: &str) -> String {
+  format!("{DOCS_RS_URL}/{lib_name}/{version}")
+}
+
+/// File name prefix used by rustdoc for pages of the given kind.
+fn page_prefix(kind: ItemKind) -> Option<&'static str> {
+  match kind {
+    ItemKind::Struct => Some("struct"),
+    ItemKind::Union => Some("union"),
+    ItemKind::Enum => Some("enum"),
+    ItemKind::Function => Some("fn"),
+    ItemKind::TypeAlias => Some("type"),
+    ItemKind::Constant => Some("constant"),
+    ItemKind::Trait => Some("trait"),
+    ItemKind::TraitAlias => Some("traitalias"),
+    ItemKind::Static => Some("static"),
+    ItemKind::ExternType => Some("foreigntype"),
+    ItemKind::Macro => Some("macro"),
+    ItemKind::ProcAttribute => Some("attr"),
+    ItemKind::ProcDerive => Some("derive"),
+    ItemKind::Primitive => Some("primitive"),
+    ItemKind::Keyword => Some("keyword"),
+    ItemKind::Module
+    | ItemKind::ExternCrate
+    | ItemKind::Use
+    | ItemKind::StructField
+    | ItemKind::Variant
+    | ItemKind::Impl
+    | ItemKind::AssocConst
+    | ItemKind::AssocType => None,
+  }
+}
+
+/// Anchor rustdoc gives a member on the page of its parent, e.g.
+/// `method.new` or `structfield.name`.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the member
+/// * `name` - The name of the member
+/// * `required` - Whether the member is a trait method without a default body
+///
+/// # Returns
+///
+/// * `Option<String>` - The anchor, without `#`, or `None` for kinds that
+///   are not members
+pub fn member_anchor(
+  kind: ItemKind,
+  name: &str,
+  required: bool,
+) -> Option<String> {
+  let prefix = match kind {
+    ItemKind::Function if required => "tymethod",
+    ItemKind::Function => "method",
+    ItemKind::StructField => "structfield",
+    ItemKind::Variant => "variant",
+    ItemKind::AssocType => "associatedtype",
+    ItemKind::AssocConst => "associatedconstant",
+    _ => return None,
+  };
+  Some(format!("{prefix}.{name}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+
+  fn summary(path: &[&str], kind: ItemKind) -> ItemSummary {
+    ItemSummary {
+      crate_id: 0,
+      path: path.iter().map(|part| part.to_string()).collect(),
+      kind,
+    }
+  }
+
+  #[test]
+  fn test_item_url_kinds() {
+    assert_eq!(
+      item_url(
+        "demo",
+        "0.3.1",
+        &summary(&["demo", "parse"], ItemKind::Function)
+      )
+      .unwrap(),
+      "https://docs.rs/demo/0.3.1/demo/fn.parse.html"
+    );
+    assert_eq!(
+      item_url(
+        "demo",
+        "0.3.1",
+        &summary(&["demo", "parser"], ItemKind::Module)
+      )
+      .unwrap(),
+      "https://docs.rs/demo/0.3.1/demo/parser/index.html"
+    );
+    assert_eq!(
+      item_url(
+        "demo",
+        "0.3.1",
+        &summary(&["demo", "parser", "Token", "Eof"], ItemKind::Variant)
+      )
+      .unwrap(),
+      "https://docs.rs/demo/0.3.1/demo/parser/enum.Token.html#variant.Eof"
+    );
+    assert_eq!(
+      item_url(
+        "demo",
+        "0.3.1",
+        &summary(&["demo", "tokens"], ItemKind::Macro)
+      )
+      .unwrap(),
+      "https://docs.rs/demo/0.3.1/demo/macro.tokens.html"
+    );
+    assert!(item_url(
+      "demo",
+      "0.3.1",
+      &summary(&["demo", "x"], ItemKind::Impl)
+    )
+    .is_none());
+  }
+
+  #[test]
+  fn test_external_item_url() {
+    let vec = ItemSummary {
+      crate_id: 3,
+      path: vec!["alloc".into(), "vec".into(), "Vec".into()],
+      kind: ItemKind::Struct,
+    };
+    let alloc = ExternalCrate {
+      name: "alloc".to_string(),
+      html_root_url: Some("https://doc.rust-lang.org/1.90.0/".to_string()),
+    };
+    assert_eq!(
+      external_item_url(&alloc, &vec).unwrap(),
+      "https://doc.rust-lang.org/1.90.0/alloc/vec/struct.Vec.html"
+    );
+
+    let unhosted = ExternalCrate {
+      name: "alloc".to_string(),
+      html_root_url: None,
+    };
+    assert_eq!(
+      external_item_url(&unhosted, &vec).unwrap(),
+      "https://docs.rs/alloc/latest/alloc/vec/struct.Vec.html"
+    );
+  }
+
+  #[test]
+  fn test_source_url_line() {
+    let span = Span {
+      filename: PathBuf::from("src/parser.rs"),
+      begin: (12, 0),
+      end: (20, 1),
+    };
+    assert_eq!(
+      source_url("demo", "0.3.1", &span),
+      "https://docs.rs/crate/demo/0.3.1/source/src/parser.rs#12"
+    );
+  }
+}
@@ -0,0 +1,269 @@
+//! # Version Resolution
+//!
+//! docs.rs accepts `latest` and semver requirements such as `^1.2` or `~4.5`
+//! in place of a version, but the documentation it serves does not always
+//! record the version it was built for. This module resolves such requests
+//! to an exact version against the crates.io index before fetching, so that
+//! `CrateDocs::version` and the links built from it are always concrete.
+
+use std::collections::HashMap;
+
+use rustdoc_types::{ExternalCrate, Id, ItemSummary};
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::fetch_docs::OnlineDocs;
+use crate::temp_trait::{CommonCrates, Item};
+
+/// Base URL of the crates.io sparse index
+const INDEX_BASE_URL: &str = "https://index.crates.io";
+
+/// A release of a crate, as listed in the crates.io index.
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+  vers: String,
+  #[serde(default)]
+  yanked: bool,
+}
+
+/// The exact version named by a request, e.g. `1.2.3` or `=1.2.3`.
+///
+/// # Returns
+///
+/// * `Option<String>` - The version; `None` for `latest` and requirements
+///   such as `^1.2`, which have to be resolved
+pub fn exact_version(version: &str) -> Option<String> {
+  let version = version.trim();
+  let version = version.strip_prefix('=').unwrap_or(version).trim();
+  Version::parse(version)
+    .ok()
+    .map(|version| version.to_string())
+}
+
+/// Resolve `latest` or a semver requirement to an exact version of a crate.
+///
+/// Exact versions are returned as is, without looking up the index.
+///
+/// # Arguments
+///
+/// * `online` - The client the index is fetched with
+/// * `lib_name` - The name of the crate (e.g., "clap", "serde")
+/// * `version` - `latest`, an exact version or a semver requirement
+///
+/// # Returns
+///
+/// * `Result<String>` - The newest release that is not yanked and matches
+///   the request; `latest` prefers stable releases over pre-releases
+///
+/// # Errors
+///
+/// * `Error::Network` - If the crates.io index cannot be fetched
+/// * `Error::Json` - If the index entry of the crate is malformed
+/// * `Error::Config` - If the crate name is not valid, the request is not a
+///   valid requirement, or no release matches it
+///
+/// # Examples
+///
+/// ```no_run
+/// use crates_llms_txt::fetch_docs::OnlineDocs;
+/// use crates_llms_txt::versions::resolve_version;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let online = OnlineDocs::default();
+///   let version = resolve_version(&online, "clap", "~4.5").await?;
+///   assert!(version.starts_with("4.5."));
+///
+///   Ok(())
+/// }
+/// ```
+///
+pub async fn resolve_version(
+  online: &OnlineDocs,
+  lib_name: &str,
+  version: &str,
+) -> Result<String> {
+  if !is_crate_name(lib_name) {
+    return Err(Error::Config(format!("invalid crate name `{lib_name}`")));
+  }
+  if let Some(version) = exact_version(version) {
+    return Ok(version);
+  }
+  let url = format!("{INDEX_BASE_URL}/{}", index_path(lib_name));
+  let index = online
+    .get(&url)
+    .send()
+    .await?
+    .error_for_status()?
+    .text()
+    .await?;
+  let versions = index
+    .lines()
+    .filter(|line| !line.trim().is_empty())
+    .map(serde_json::from_str::<IndexEntry>)
+    .collect::<serde_json::Result<Vec<_>>>()?
+    .into_iter()
+    .filter(|entry| !entry.yanked)
+    .map(|entry| entry.vers);
+  select_version(versions, version)?.ok_or_else(|| {
+    Error::Config(format!("no release of {lib_name} matches `{version}`"))
+  })
+}
+
+/// Pick the newest version matching `latest` or a semver requirement.
+///
+/// # Arguments
+///
+/// * `versions` - The candidate versions; ones that are not valid semver are
+///   skipped
+/// * `requirement` - `latest` or a semver requirement, e.g. `^1.2`
+///
+/// # Errors
+///
+/// * `Error::Config` - If the requirement is not valid
+pub fn select_version(
+  versions: impl IntoIterator<Item = String>,
+  requirement: &str,
+) -> Result<Option<String>> {
+  let versions = versions
+    .into_iter()
+    .filter_map(|version| Version::parse(&version).ok());
+  let selected = if requirement.trim() == "latest" {
+    // Like docs.rs, prefer the newest stable release
+    versions.max_by(|a, b| (a.pre.is_empty(), a).cmp(&(b.pre.is_empty(), b)))
+  } else {
+    let requirement = VersionReq::parse(requirement).map_err(|err| {
+      Error::Config(format!(
+        "invalid version requirement `{requirement}`: {err}"
+      ))
+    })?;
+    versions
+      .filter(|version| requirement.matches(version))
+      .max()
+  };
+  Ok(selected.map(|version| version.to_string()))
+}
+
+/// Whether a name is a valid crate name: ASCII alphanumerics, `-` and `_`.
+fn is_crate_name(name: &str) -> bool {
+  !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
+/// Path of a crate in the crates.io sparse index, e.g. `se/rd/serde`.
+///
+/// The name must be a valid crate name, see `is_crate_name`.
+fn index_path(lib_name: &str) -> String {
+  let name = lib_name.to_lowercase();
+  match name.len() {
+    1 => format!("1/{name}"),
+    2 => format!("2/{name}"),
+    3 => format!("3/{}/{name}", &name[..1]),
+    _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+  }
+}
+
+/// Documentation fetched for a resolved version, which reports that version
+/// whatever its rustdoc JSON records.
+pub(crate) struct ResolvedCrate {
+  /// The documentation
+  pub docs: Box<dyn CommonCrates>,
+  /// The resolved version
+  pub version: String,
+}
+
+impl CommonCrates for ResolvedCrate {
+  fn root_id(&self) -> Id {
+    self.docs.root_id()
+  }
+
+  fn crate_version(&self) -> Option<String> {
+    Some(self.version.clone())
+  }
+
+  fn root_name(&self) -> Option<String> {
+    self.docs.root_name()
+  }
+
+  fn index(&self) -> HashMap<Id, Item> {
+    self.docs.index()
+  }
+
+  fn paths(&self) -> HashMap<Id, ItemSummary> {
+    self.docs.paths()
+  }
+
+  fn external_crates(&self) -> HashMap<u32, ExternalCrate> {
+    self.docs.external_crates()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_exact_version() {
+    assert_eq!(exact_version("1.2.3").as_deref(), Some("1.2.3"));
+    assert_eq!(exact_version("=1.0.0-rc.1").as_deref(), Some("1.0.0-rc.1"));
+    assert_eq!(exact_version("latest"), None);
+    assert_eq!(exact_version("^1.2"), None);
+    assert_eq!(exact_version("1.2"), None);
+  }
+
+  #[test]
+  fn test_select_version() {
+    let versions = || {
+      ["1.2.0", "1.2.9", "1.3.1", "2.0.0-beta.1", "not-a-version"]
+        .map(String::from)
+    };
+    let select = |requirement| select_version(versions(), requirement).unwrap();
+
+    assert_eq!(select("latest").as_deref(), Some("1.3.1"));
+    assert_eq!(select("^1.2").as_deref(), Some("1.3.1"));
+    assert_eq!(select("~1.2").as_deref(), Some("1.2.9"));
+    assert_eq!(select(">=2.0.0-beta.1").as_deref(), Some("2.0.0-beta.1"));
+    assert_eq!(select("^3"), None);
+    assert!(select_version(versions(), "one").is_err());
+
+    // Pre-releases are `latest` only when there is no stable release
+    let pre = ["0.1.0-alpha.1", "0.1.0-alpha.2"].map(String::from);
+    assert_eq!(
+      select_version(pre, "latest").unwrap().as_deref(),
+      Some("0.1.0-alpha.2")
+    );
+  }
+
+  #[test]
+  fn test_is_crate_name() {
+    assert!(is_crate_name("serde_json"));
+    assert!(is_crate_name("rustdoc-types"));
+    assert!(!is_crate_name(""));
+    assert!(!is_crate_name("sérde"));
+    assert!(!is_crate_name("../serde"));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_version_rejects_invalid_names() {
+    let online = OnlineDocs::default();
+    for name in ["sérde", "日本語", ""] {
+      let Err(Error::Config(msg)) =
+        resolve_version(&online, name, "latest").await
+      else {
+        panic!("`{name}` should be rejected");
+      };
+      assert_eq!(msg, format!("invalid crate name `{name}`"));
+    }
+  }
+
+  #[test]
+  fn test_index_path() {
+    assert_eq!(index_path("a"), "1/a");
+    assert_eq!(index_path("io"), "2/io");
+    assert_eq!(index_path("syn"), "3/s/syn");
+    assert_eq!(index_path("Serde"), "se/rd/serde");
+  }
+}
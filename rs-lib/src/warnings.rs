@@ -0,0 +1,74 @@
+//! # Processing Warnings
+//!
+//! Rustdoc JSON is not always complete: items generated by macros or pulled
+//! in through re-exports may come without a source span, and file names are
+//! not guaranteed to be valid UTF-8. Instead of aborting, processing falls back
+//! to a degraded link or title and records a [`Warning`] on the resulting
+//! `CrateDocs`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of problem a [`Warning`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+  /// The item has no source span, so it has no source link
+  MissingSpan,
+  /// The source file name of the item is not valid UTF-8
+  NonUtf8Path,
+  /// Neither a docs.rs page nor a source view exists for the item; the
+  /// session links to the crate landing page instead
+  MissingLink,
+  /// The item has neither a path nor a name; the session is titled by its id
+  MissingTitle,
+}
+
+impl WarningKind {
+  /// Stable lowercase identifier, matching the serialized form.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      WarningKind::MissingSpan => "missing_span",
+      WarningKind::NonUtf8Path => "non_utf8_path",
+      WarningKind::MissingLink => "missing_link",
+      WarningKind::MissingTitle => "missing_title",
+    }
+  }
+}
+
+/// A non-fatal problem encountered while processing an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+  /// What went wrong
+  pub kind: WarningKind,
+  /// Id of the item in the rustdoc index
+  pub item_id: u32,
+  /// Title of the session generated for the item
+  pub item: String,
+  /// Human-readable description of the problem and the fallback used
+  pub message: String,
+}
+
+impl Warning {
+  /// Creates a warning for the item with the given id and session title.
+  pub fn new(
+    kind: WarningKind,
+    item_id: u32,
+    item: &str,
+    message: impl Into<String>,
+  ) -> Self {
+    Self {
+      kind,
+      item_id,
+      item: item.to_string(),
+      message: message.into(),
+    }
+  }
+}
+
+impl fmt::Display for Warning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} (item {}): {}", self.item, self.item_id, self.message)
+  }
+}
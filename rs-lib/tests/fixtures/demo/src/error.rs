@@ -0,0 +1,27 @@
+//! Error types.
+
+use std::fmt;
+
+/// The error returned when parsing fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Error {
+  /// Byte offset of the failure.
+  pub offset: usize,
+  message: String,
+}
+
+impl Error {
+  /// Create a new error at `offset`.
+  pub fn new(offset: usize, message: impl Into<String>) -> Self {
+    Self { offset, message: message.into() }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at {}", self.message, self.offset)
+  }
+}
+
+impl std::error::Error for Error {}
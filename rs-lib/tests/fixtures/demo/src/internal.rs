@@ -0,0 +1,6 @@
+/// A type defined in a private module and re-exported at the crate root.
+#[derive(Debug, Default)]
+pub struct Reexported {
+  /// The wrapped value.
+  pub value: u32,
+}
@@ -0,0 +1,82 @@
+//! A small parsing library used as a rustdoc JSON fixture.
+//!
+//! Start with [`Parser`] and inspect the produced [`Token`]s. Results use
+//! [`Result`], and collections are plain [`Vec`]s.
+//!
+//! ```
+//! let parser = demo::Parser::new("a b");
+//! ```
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub mod error;
+mod internal;
+pub mod parser;
+
+pub use internal::Reexported;
+pub use parser::{Parser, Token};
+
+/// Result type used throughout the crate.
+pub type Result<T> = std::result::Result<T, error::Error>;
+
+/// Maximum number of tokens produced by a single parse.
+pub const MAX_TOKENS: usize = 1024;
+
+/// Global verbosity flag.
+pub static VERBOSE: bool = false;
+
+/// Parse `input` with a default [`Parser`].
+///
+/// Returns every token found in the input.
+pub fn parse(input: &str) -> Result<Vec<Token>> {
+  Parser::new(input).parse()
+}
+
+/// Parse `input` using the old tokenizer.
+#[deprecated(since = "0.2.0", note = "use `parse` instead")]
+pub fn old_parse(input: &str) -> Vec<Token> {
+  parse(input).unwrap_or_default()
+}
+
+/// Count the tokens in `input`.
+#[must_use]
+pub fn count<'a, I>(input: I) -> usize
+where
+  I: IntoIterator<Item = &'a str>,
+{
+  input.into_iter().count()
+}
+
+/// Parse `input` asynchronously.
+pub async fn parse_async(input: &str, limit: Option<usize>) -> Result<Vec<Token>> {
+  let mut tokens = parse(input)?;
+  tokens.truncate(limit.unwrap_or(MAX_TOKENS));
+  Ok(tokens)
+}
+
+/// Serialize tokens as JSON.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub fn to_json(tokens: &[Token]) -> String {
+  format!("{}", tokens.len())
+}
+
+/// Support function for the macros of this crate.
+#[doc(hidden)]
+pub fn __private_support() {}
+
+pub fn undocumented(value: u8) -> u8 {
+  value
+}
+
+/// Create a `Vec` of tokens.
+///
+/// # Examples
+///
+/// ```
+/// let tokens = demo::tokens!["a", "b"];
+/// ```
+#[macro_export]
+macro_rules! tokens {
+  () => { Vec::new() };
+  ($($word:expr),+ $(,)?) => { vec![$($crate::Token::Ident($word.to_string())),+] };
+}
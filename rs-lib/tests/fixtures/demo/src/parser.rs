@@ -0,0 +1,97 @@
+//! The tokenizer and its [`Token`] type.
+
+use crate::error;
+
+/// A whitespace tokenizer.
+///
+/// See [`Parser::parse`] and the [`Visitor`] trait.
+#[derive(Debug, Clone, Default)]
+pub struct Parser<'a> {
+  /// The input being parsed.
+  pub input: &'a str,
+  /// Whether empty tokens are kept.
+  pub keep_empty: bool,
+  position: usize,
+}
+
+impl<'a> Parser<'a> {
+  /// Create a parser over `input`.
+  pub fn new(input: &'a str) -> Self {
+    Self { input, keep_empty: false, position: 0 }
+  }
+
+  /// Parse the whole input into tokens.
+  pub fn parse(&mut self) -> crate::Result<Vec<Token>> {
+    let tokens = self
+      .input
+      .split_whitespace()
+      .map(|word| Token::Ident(word.to_string()))
+      .collect();
+    self.position = self.input.len();
+    Ok(tokens)
+  }
+
+  /// Current byte position of the parser.
+  pub fn position(&self) -> usize {
+    self.position
+  }
+
+  fn reset(&mut self) {
+    self.position = 0;
+  }
+}
+
+impl<'a> From<&'a str> for Parser<'a> {
+  fn from(input: &'a str) -> Self {
+    Self::new(input)
+  }
+}
+
+/// A single token produced by the [`Parser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Token {
+  /// An identifier.
+  Ident(String),
+  /// A number literal.
+  Number {
+    /// The parsed value.
+    value: i64,
+    /// Whether the literal had a sign.
+    signed: bool,
+  },
+  /// End of input.
+  Eof,
+}
+
+/// Walks over parsed tokens.
+pub trait Visitor: Clone {
+  /// Value produced by the visitor.
+  type Output;
+
+  /// Number of tokens visited at most.
+  const LIMIT: usize = 16;
+
+  /// Visit a single token.
+  fn visit(&mut self, token: &Token) -> Option<Self::Output>;
+
+  /// Visit every token in order.
+  fn visit_all(&mut self, tokens: &[Token]) -> Vec<Self::Output> {
+    tokens.iter().filter_map(|token| self.visit(token)).collect()
+  }
+}
+
+impl<'a> Visitor for Parser<'a> {
+  type Output = String;
+
+  fn visit(&mut self, token: &Token) -> Option<Self::Output> {
+    match token {
+      Token::Ident(name) => Some(name.clone()),
+      _ => None,
+    }
+  }
+}
+
+/// Parser-specific error, distinct from [`crate::error::Error`].
+#[derive(Debug)]
+pub struct Error(pub error::Error);
@@ -0,0 +1,63 @@
+#!/usr/bin/env sh
+# Regenerate the rustdoc JSON fixtures used by the unit tests.
+#
+# Each fixture is the output of a toolchain whose rustdoc emits the
+# format_version it is named after:
+#
+# | Fixture          | Toolchain          | format_version |
+# |------------------|--------------------|----------------|
+# | `demo.v43.json`  | 1.87               | 43             |
+# | `demo.v45.json`  | 1.88               | 45             |
+# | `demo.v53.json`  | 1.89               | 53             |
+# | `demo.json`      | 1.90               | 55             |
+# | `demo.v57.json`  | nightly-2026-05-20 | 57             |
+#
+# `demo.json` is the version understood by `rustdoc-types` 0.55. The `paths`
+# table is pruned to the ids referenced by the crate to keep them small.
+set -eu
+cd "$(dirname "$0")/demo"
+
+build() {
+  RUSTC_BOOTSTRAP=1 RUSTDOCFLAGS="--cfg docsrs" cargo "+$1" rustdoc -q \
+    --all-features -- -Z unstable-options --output-format json
+
+  python3 - target/doc/demo.json "$2" <<'PY'
+import json, os, sys
+
+data = json.load(open(sys.argv[1]))
+referenced = set()
+
+def walk(value):
+    if isinstance(value, dict):
+        for key, inner in value.items():
+            if key in ("id", "trait") and isinstance(inner, int):
+                referenced.add(str(inner))
+            walk(inner)
+    elif isinstance(value, list):
+        for inner in value:
+            walk(inner)
+
+walk(data["index"])
+for item in data["index"].values():
+    referenced.update(str(target) for target in item["links"].values())
+
+data["paths"] = {
+    key: summary
+    for key, summary in data["paths"].items()
+    if summary["crate_id"] == 0 or key in referenced
+}
+# Format 56 added the path of external crates, which is machine-specific
+for external in data["external_crates"].values():
+    if "path" in external:
+        external["path"] = os.path.basename(external["path"])
+json.dump(data, open(sys.argv[2], "w"), separators=(",", ":"), sort_keys=True)
+PY
+
+  rm -rf target Cargo.lock
+}
+
+build 1.87 ../demo.v43.json
+build 1.88 ../demo.v45.json
+build 1.89 ../demo.v53.json
+build 1.90 ../demo.json
+build nightly-2026-05-20 ../demo.v57.json